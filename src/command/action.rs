@@ -1,19 +1,31 @@
 use clap::builder::Str;
 
 use crate::{
-    command::application::{CLEAR, CREATE, DELETE, EXIT, HISTORY, LIST, LS, Q, QUEUE, TEST},
+    command::application::{
+        CLEAR, CREATE, CYCLE, DELETE, EXIT, EXTEND, HISTORY, LIST, LS, PAUSE, Q, QUEUE, RESUME,
+        SKIP, SNOOZE, STATS, STATUS, SUBSCRIBE, TEST,
+    },
     error::ParseError,
 };
 
 pub enum ActionType {
     Create,
     Queue,
+    Cycle,
     Delete,
+    Pause,
+    Resume,
+    Extend,
+    Snooze,
+    Skip,
     List,
     Test,
     Exit,
     Clear,
     History,
+    Stats,
+    Status,
+    Subscribe,
 }
 
 impl ActionType {
@@ -21,12 +33,21 @@ impl ActionType {
         match s.to_lowercase().as_str() {
             CREATE => Ok(ActionType::Create),
             Q | QUEUE => Ok(ActionType::Queue),
+            CYCLE => Ok(ActionType::Cycle),
             DELETE => Ok(ActionType::Delete),
+            PAUSE => Ok(ActionType::Pause),
+            RESUME => Ok(ActionType::Resume),
+            EXTEND => Ok(ActionType::Extend),
+            SNOOZE => Ok(ActionType::Snooze),
+            SKIP => Ok(ActionType::Skip),
             LS | LIST => Ok(ActionType::List),
             TEST => Ok(ActionType::Test),
             EXIT => Ok(ActionType::Exit),
             CLEAR => Ok(ActionType::Clear),
             HISTORY => Ok(ActionType::History),
+            STATS => Ok(ActionType::Stats),
+            STATUS => Ok(ActionType::Status),
+            SUBSCRIBE => Ok(ActionType::Subscribe),
             _ => Err(ParseError::new(format!(
                 "failed to parse str ({}) to ActionType",
                 s
@@ -40,12 +61,21 @@ impl From<ActionType> for String {
         match action {
             ActionType::Create => String::from(CREATE),
             ActionType::Queue => String::from(QUEUE),
+            ActionType::Cycle => String::from(CYCLE),
             ActionType::Delete => String::from(DELETE),
+            ActionType::Pause => String::from(PAUSE),
+            ActionType::Resume => String::from(RESUME),
+            ActionType::Extend => String::from(EXTEND),
+            ActionType::Snooze => String::from(SNOOZE),
+            ActionType::Skip => String::from(SKIP),
             ActionType::List => String::from(LIST),
             ActionType::Test => String::from(TEST),
             ActionType::Exit => String::from(EXIT),
             ActionType::Clear => String::from(CLEAR),
             ActionType::History => String::from(HISTORY),
+            ActionType::Stats => String::from(STATS),
+            ActionType::Status => String::from(STATUS),
+            ActionType::Subscribe => String::from(SUBSCRIBE),
         }
     }
 }
@@ -55,12 +85,21 @@ impl From<ActionType> for Str {
         match action {
             ActionType::Create => CREATE.into(),
             ActionType::Queue => QUEUE.into(),
+            ActionType::Cycle => CYCLE.into(),
             ActionType::Delete => DELETE.into(),
+            ActionType::Pause => PAUSE.into(),
+            ActionType::Resume => RESUME.into(),
+            ActionType::Extend => EXTEND.into(),
+            ActionType::Snooze => SNOOZE.into(),
+            ActionType::Skip => SKIP.into(),
             ActionType::List => LIST.into(),
             ActionType::Test => TEST.into(),
             ActionType::Exit => EXIT.into(),
             ActionType::Clear => CLEAR.into(),
             ActionType::History => HISTORY.into(),
+            ActionType::Stats => STATS.into(),
+            ActionType::Status => STATUS.into(),
+            ActionType::Subscribe => SUBSCRIBE.into(),
         }
     }
 }
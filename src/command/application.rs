@@ -1,4 +1,5 @@
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::command::action::ActionType;
@@ -14,16 +15,29 @@ pub const TEST: &str = "test";
 pub const EXIT: &str = "exit";
 pub const CLEAR: &str = "clear";
 pub const HISTORY: &str = "history";
+pub const PAUSE: &str = "pause";
+pub const RESUME: &str = "resume";
+pub const EXTEND: &str = "extend";
+pub const SNOOZE: &str = "snooze";
+pub const SKIP: &str = "skip";
+pub const CYCLE: &str = "cycle";
+pub const STATS: &str = "stats";
+pub const STATUS: &str = "status";
+pub const SUBSCRIBE: &str = "subscribe";
 
 const AUTHOR: &str = "Young";
 const BINARY_NAME: &str = "pomodoro";
 
 pub const DEFAULT_WORK_TIME: u16 = 25;
 pub const DEFAULT_BREAK_TIME: u16 = 5;
+pub const DEFAULT_LONG_BREAK_TIME: u16 = 15;
+pub const DEFAULT_LONG_BREAK_INTERVAL: u16 = 4;
+pub const DEFAULT_STATUS_FORMAT: &str = "{phase} {remaining} {description}";
 
 pub enum CommandType {
-    StartUp(Arc<Configuration>),
-    UdsClient(ArgMatches),
+    // the path is the address of the server socket
+    StartUp(Arc<Configuration>, PathBuf),
+    UdsClient(ArgMatches, PathBuf),
     AutoComplete(ArgMatches),
 }
 
@@ -40,8 +54,24 @@ pub fn get_start_and_uds_client_command() -> Command {
                 .short('c')
                 .long("config"),
         )
+        .arg(
+            Arg::new("output")
+                .help("Print the result of the command as text or json")
+                .long("output")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .global(true),
+        )
+        .arg(
+            Arg::new("socket")
+                .help("Use the socket at this path, e.g. to run independent pomodoros")
+                .long("socket")
+                .num_args(1)
+                .global(true),
+        )
         .subcommands({
             let mut cmd = get_common_subcommands();
+            cmd.push(get_subscribe_command());
             cmd.push(
                 Command::new("completion")
                     .about("generate completions for shells")
@@ -57,6 +87,12 @@ pub fn get_start_and_uds_client_command() -> Command {
         })
 }
 
+// subscribe is only for the uds client, since the events are pushed to the client socket
+fn get_subscribe_command() -> Command {
+    Command::new(ActionType::Subscribe)
+        .about("print the events of notifications as they happen, until interrupted")
+}
+
 pub fn get_main_command() -> Command {
     Command::new(BINARY_NAME)
         .no_binary_name(true)
@@ -78,7 +114,7 @@ fn get_common_subcommands() -> Vec<Command> {
             let cmd = Command::new(ActionType::Create)
                 .alias("c")
                 .about("create the notification");
-            add_args_for_create_subcommand(cmd)
+            add_args_for_schedule(add_args_for_create_subcommand(cmd))
         },
         {
             let cmd = Command::new(ActionType::Queue)
@@ -86,6 +122,11 @@ fn get_common_subcommands() -> Vec<Command> {
                 .about("create the notification");
             add_args_for_create_subcommand(cmd)
         },
+        {
+            let cmd = Command::new(ActionType::Cycle)
+                .about("create the pomodoros of a cycle with a long break after every few of them");
+            add_args_for_cycle_subcommand(cmd)
+        },
         Command::new(ActionType::Delete)
             .alias("d")
             .about("delete a notification")
@@ -104,6 +145,79 @@ fn get_common_subcommands() -> Vec<Command> {
                     .num_args(0)
                     .long("all"),
             ),
+        Command::new(ActionType::Pause)
+            .about("pause a notification, freezing its remaining time")
+            .arg(
+                Arg::new("id")
+                    .help("The ID of notification to pause")
+                    .num_args(1)
+                    .required(true)
+                    .short('i')
+                    .long("id"),
+            ),
+        Command::new(ActionType::Resume)
+            .about("resume a paused notification, postponing the queued notifications behind it")
+            .arg(
+                Arg::new("id")
+                    .help("The ID of notification to resume")
+                    .num_args(1)
+                    .required(true)
+                    .short('i')
+                    .long("id"),
+            ),
+        Command::new(ActionType::Extend)
+            .about("add time to the current work or break of a notification")
+            .arg(
+                Arg::new("id")
+                    .help("The ID of notification to extend")
+                    .num_args(1)
+                    .required(true)
+                    .short('i')
+                    .long("id"),
+            )
+            .arg(
+                Arg::new("minutes")
+                    .help("The time to add to the current phase, in minutes or like 1h30m")
+                    .num_args(1)
+                    .required(true)
+                    .short('m')
+                    .long("minutes"),
+            ),
+        Command::new(ActionType::Snooze)
+            .about("fire the break notification again after some minutes")
+            .arg(
+                Arg::new("id")
+                    .help("The ID of notification to snooze, which can be already finished")
+                    .num_args(1)
+                    .required(true)
+                    .short('i')
+                    .long("id"),
+            )
+            .arg(
+                Arg::new("minutes")
+                    .help("The time to wait before the break notification fires again, in minutes or like 90s")
+                    .num_args(1)
+                    .default_value("5")
+                    .short('m')
+                    .long("minutes"),
+            ),
+        Command::new(ActionType::Skip)
+            .about("end the current work or break of a notification now")
+            .arg(
+                Arg::new("id")
+                    .help("The ID of notification to skip")
+                    .num_args(1)
+                    .required(true)
+                    .short('i')
+                    .long("id"),
+            )
+            .arg(
+                Arg::new("silent")
+                    .help("The flag to skip without sending the notification")
+                    .num_args(0)
+                    .short('s')
+                    .long("silent"),
+            ),
         Command::new(ActionType::List)
             .alias(LS)
             .about("list notifications")
@@ -112,6 +226,12 @@ fn get_common_subcommands() -> Vec<Command> {
                     .short('p')
                     .help("show work time completion percentage")
                     .num_args(0),
+            )
+            .arg(
+                Arg::new("tz")
+                    .help("The IANA timezone to show the timestamps in, like Asia/Seoul")
+                    .num_args(1)
+                    .long("tz"),
             ),
         Command::new(ActionType::History)
             .about("show archived notifications")
@@ -121,6 +241,97 @@ fn get_common_subcommands() -> Vec<Command> {
                     .short('c')
                     .num_args(0)
                     .long("clear"),
+            )
+            .arg(
+                Arg::new("export")
+                    .help("Write all notifications from history to this path")
+                    .num_args(1)
+                    .conflicts_with("clear")
+                    .long("export"),
+            )
+            .arg(
+                Arg::new("import")
+                    .help("Read notifications from this csv or json file into history, skipping the ones already in it")
+                    .num_args(1)
+                    .conflicts_with("clear")
+                    .conflicts_with("export")
+                    .long("import"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long_help(
+                        "The format of the exported or imported file, which is one of csv, json and ics.
+If no value is passed, it is obtained from the extension of the path. ics can not be imported.
+",
+                    )
+                    .num_args(1)
+                    .value_parser(["csv", "json", "ics"])
+                    .requires("file")
+                    .long("format"),
+            )
+            .group(ArgGroup::new("file").args(["export", "import"]))
+            .arg(
+                Arg::new("tz")
+                    .help("The IANA timezone to show the timestamps in, like Asia/Seoul")
+                    .num_args(1)
+                    .long("tz"),
+            ),
+        Command::new(ActionType::Stats)
+            .about("show focus totals and streaks of archived notifications")
+            .arg(
+                Arg::new("days")
+                    .help("The number of recent days to show the focused time of")
+                    .num_args(1)
+                    .short('d')
+                    .long("days"),
+            )
+            .arg(
+                Arg::new("heatmap")
+                    .help("The flag to show the focused time per day of the last year as a heatmap")
+                    .num_args(0)
+                    .long("heatmap"),
+            )
+            .arg(
+                Arg::new("plain")
+                    .help("The flag to draw the heatmap with plain characters instead of colors")
+                    .num_args(0)
+                    .requires("heatmap")
+                    .long("plain"),
+            )
+            .arg(
+                Arg::new("tz")
+                    .help("The IANA timezone to split the days in, like Asia/Seoul")
+                    .num_args(1)
+                    .long("tz"),
+            ),
+        Command::new(ActionType::Status)
+            .about("show the active notification in a line for status bars")
+            .arg(
+                Arg::new("format")
+                    .long_help(
+                        "The format of the line, which is a template or one of waybar and i3bar.
+The template replaces {id}, {phase}, {remaining}, {percentage}, {description} and {cycle}.
+waybar and i3bar print the json which the bars read from a custom module or block.
+",
+                    )
+                    .num_args(1)
+                    .short('f')
+                    .long("format")
+                    .default_value(DEFAULT_STATUS_FORMAT),
+            )
+            .arg(
+                Arg::new("watch")
+                    .help("The flag to keep printing the updated line, which is for the uds client")
+                    .num_args(0)
+                    .long("watch"),
+            )
+            .arg(
+                Arg::new("interval")
+                    .help("The interval to print the line in --watch mode, like 1s or 1m")
+                    .num_args(1)
+                    .requires("watch")
+                    .long("interval")
+                    .default_value("1s"),
             ),
         Command::new(ActionType::Test).about("test notification"),
     ]
@@ -146,6 +357,13 @@ And if no configuration file is passed or `break_time_default_value` is not pres
                 .num_args(1)
                 .short('b'),
         )
+        .arg(
+            Arg::new("message")
+                .help("The description of the notification, which is also used in the notification text")
+                .num_args(1)
+                .short('m')
+                .long("message"),
+        )
         .arg(
             Arg::new("default")
                 .long_help(
@@ -161,6 +379,59 @@ If no configuration file is passed or the keys are not present, then 25 minutes
         )
 }
 
+pub(crate) fn add_args_for_schedule(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("at")
+                .long_help(
+                    "The time to start the notification, like `14:00` or `2026-10-18 09:30`.
+If only the time is passed and it is already passed today, the notification starts tomorrow.
+",
+                )
+                .num_args(1)
+                .conflicts_with("in")
+                .long("at"),
+        )
+        .arg(
+            Arg::new("in")
+                .help("The duration to wait before starting the notification, like `20m`")
+                .num_args(1)
+                .long("in"),
+        )
+        .arg(
+            Arg::new("tz")
+                .long_help("The IANA timezone of `--at`, like Asia/Seoul.
+If no value is passed, `timezone` in the given configuration file is used, and then the local timezone.
+")
+                .num_args(1)
+                .requires("at")
+                .long("tz"),
+        )
+}
+
+pub(crate) fn add_args_for_cycle_subcommand(command: Command) -> Command {
+    add_args_for_create_subcommand(command)
+        .arg(
+            Arg::new("count")
+                .long_help("The number of pomodoros in the cycle.
+If no value is passed, `long_break_interval` in the given configuration file is used, which is 4 by default.
+")
+                .num_args(1)
+                .short('n')
+                .long("count"),
+        )
+        .arg(
+            Arg::new("long_break")
+                .long_help("The long break time in minutes, taken after every `long_break_interval` pomodoros.
+If no value is passed, the long break time is obtained from `long_break_time_default_value` in the given configuration file.
+And if no configuration file is passed or `long_break_time_default_value` is not present, then 15 is used as the long break time.
+")
+                .num_args(1)
+                .short('l')
+                .long("long-break"),
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use super::{get_start_and_uds_client_command, AUTHOR, BINARY_NAME};
@@ -168,7 +439,10 @@ mod tests {
 
     use crate::command::application::get_common_subcommands;
 
-    use super::{add_args_for_create_subcommand, get_main_command};
+    use super::{
+        add_args_for_create_subcommand, add_args_for_schedule, get_main_command,
+        get_subscribe_command,
+    };
 
     #[test]
     fn test_get_start_and_uds_client_command() {
@@ -179,6 +453,7 @@ mod tests {
 
         let uds_sub_cmds = uds_cmd.get_subcommands().collect::<Vec<&Command>>();
         let mut main_sub_cmds = get_common_subcommands();
+        main_sub_cmds.push(get_subscribe_command());
         main_sub_cmds.push(completion_cmd);
 
         assert_eq!(uds_cmd.get_name(), BINARY_NAME);
@@ -218,6 +493,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_output_arg() {
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro ls -p --output json".split_whitespace())
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!("json", sub_matches.get_one::<String>("output").unwrap());
+
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro history".split_whitespace())
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!("text", sub_matches.get_one::<String>("output").unwrap());
+
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro -c config.json".split_whitespace());
+        assert!(matches.is_ok());
+
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro ls --output yaml".split_whitespace());
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    fn test_socket_arg() {
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro --socket work.sock".split_whitespace())
+            .unwrap();
+        assert_eq!("work.sock", matches.get_one::<String>("socket").unwrap());
+
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro ls --socket work.sock".split_whitespace())
+            .unwrap();
+        assert_eq!("work.sock", matches.get_one::<String>("socket").unwrap());
+    }
+
+    #[test]
+    fn test_history_format_arg() {
+        for args in [
+            "pomodoro history --export h.json --format csv",
+            "pomodoro history --import h.json --format csv",
+        ] {
+            let matches =
+                get_start_and_uds_client_command().try_get_matches_from(args.split_whitespace());
+            assert!(matches.is_ok(), "{}", args);
+        }
+
+        let matches = get_start_and_uds_client_command()
+            .try_get_matches_from("pomodoro history --format csv".split_whitespace());
+        assert!(matches.is_err());
+    }
+
     #[test]
     fn test_get_main_command() {
         let app = get_main_command();
@@ -234,7 +561,79 @@ mod tests {
     #[test]
     fn test_get_common_subcommands() {
         let subcommands = get_common_subcommands();
-        assert_eq!(subcommands.len(), 6);
+        assert_eq!(subcommands.len(), 14);
+    }
+
+    #[test]
+    fn test_stats_command() {
+        let cmd = get_main_command();
+        let matches = cmd
+            .try_get_matches_from("stats -d 30 --tz Asia/Seoul".split_whitespace())
+            .unwrap();
+        let (name, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!("stats", name);
+        assert_eq!("30", sub_matches.get_one::<String>("days").unwrap());
+        assert_eq!("Asia/Seoul", sub_matches.get_one::<String>("tz").unwrap());
+        assert!(!sub_matches.get_flag("heatmap"));
+
+        let cmd = get_main_command();
+        let matches = cmd
+            .try_get_matches_from("stats --heatmap --plain".split_whitespace())
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert!(sub_matches.get_flag("heatmap"));
+        assert!(sub_matches.get_flag("plain"));
+
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("stats --plain".split_whitespace());
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    fn test_pause_and_resume_command() {
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("pause -i 1".split_whitespace());
+        assert!(matches.is_ok());
+
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("resume --id 1".split_whitespace());
+        assert!(matches.is_ok());
+
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("pause".split_whitespace());
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    fn test_extend_and_snooze_command() {
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("extend -i 1 -m 5".split_whitespace());
+        assert!(matches.is_ok());
+
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("extend -i 1".split_whitespace());
+        assert!(matches.is_err());
+
+        let cmd = get_main_command();
+        let matches = cmd
+            .try_get_matches_from("snooze -i 1".split_whitespace())
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!("5", sub_matches.get_one::<String>("minutes").unwrap());
+    }
+
+    #[test]
+    fn test_skip_command() {
+        let cmd = get_main_command();
+        let matches = cmd
+            .try_get_matches_from("skip -i 1 --silent".split_whitespace())
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert!(sub_matches.get_flag("silent"));
+
+        let cmd = get_main_command();
+        let matches = cmd.try_get_matches_from("skip".split_whitespace());
+        assert!(matches.is_err());
     }
 
     #[test]
@@ -275,5 +674,36 @@ mod tests {
         assert!(matches.get_one::<String>("work").is_none());
         assert_eq!(matches.get_flag("default"), false);
         assert!(matches.contains_id("default"));
+
+        // test message
+        let cmd = Command::new("myapp");
+        let matches = add_args_for_create_subcommand(cmd).get_matches_from([
+            "myapp",
+            "-m",
+            "review PR #42",
+            "-d",
+        ]);
+        let message = matches.get_one::<String>("message").unwrap();
+        assert!(message.eq("review PR #42"));
+        assert!(matches.get_flag("default"));
+    }
+
+    #[test]
+    fn test_add_args_for_schedule() {
+        let cmd = Command::new("myapp");
+        let matches =
+            add_args_for_schedule(cmd).get_matches_from(["myapp", "--at", "2026-10-18 09:30"]);
+        assert_eq!("2026-10-18 09:30", matches.get_one::<String>("at").unwrap());
+
+        let cmd = Command::new("myapp");
+        let matches =
+            add_args_for_schedule(cmd).get_matches_from("myapp --in 20m".split_whitespace());
+        assert_eq!("20m", matches.get_one::<String>("in").unwrap());
+
+        // only one of them can be given
+        let cmd = Command::new("myapp");
+        let matches = add_args_for_schedule(cmd)
+            .try_get_matches_from("myapp --at 14:00 --in 20m".split_whitespace());
+        assert!(matches.is_err());
     }
 }
@@ -1,14 +1,18 @@
 use crate::ipc::{Bincodec, UdsMessage};
 use clap::ArgMatches;
+use colored::control::SHOULD_COLORIZE;
+use std::env;
 use std::result;
+use std::time::Duration;
 use tokio::net::UnixDatagram;
+use tokio::time::sleep;
 
 use crate::command::action::ActionType;
+use crate::command::output::OutputFormat;
 use crate::command::util;
-use crate::error::UdsHandlerError;
-use crate::ipc::{MessageRequest, MessageResponse};
-
-pub const BUFFER_LENGTH: usize = 100_000_000;
+use crate::error::{ParseError, UdsHandlerError};
+use crate::ipc::{self, MessageRequest, MessageResponse};
+use crate::status::{StatusFormat, I3BAR_HEADER};
 
 type HandleUdsResult = result::Result<(), UdsHandlerError>;
 
@@ -22,14 +26,25 @@ pub async fn handle(matches: ArgMatches, socket: UnixDatagram) -> HandleUdsResul
                 .map(|s| (s, sub_matches))
                 .map_err(UdsHandlerError::ParseError)
         })?;
+    // `--output` is a global arg, so it is read from the subcommand
+    let output = OutputFormat::from_arg(sub_matches.get_one::<String>("output"));
 
     match action_type {
-        ActionType::Create => handle_create(socket, sub_matches).await?,
-        ActionType::Queue => handle_queue(socket, sub_matches).await?,
-        ActionType::Delete => handle_delete(socket, sub_matches).await?,
-        ActionType::List => handle_list(socket, sub_matches).await?,
-        ActionType::Test => handle_test(socket).await?,
-        ActionType::History => handle_history(socket, sub_matches).await?,
+        ActionType::Create => handle_create(socket, sub_matches, output).await?,
+        ActionType::Queue => handle_queue(socket, sub_matches, output).await?,
+        ActionType::Cycle => handle_cycle(socket, sub_matches, output).await?,
+        ActionType::Delete => handle_delete(socket, sub_matches, output).await?,
+        ActionType::Pause => handle_pause(socket, sub_matches, output).await?,
+        ActionType::Resume => handle_resume(socket, sub_matches, output).await?,
+        ActionType::Extend => handle_extend(socket, sub_matches, output).await?,
+        ActionType::Snooze => handle_snooze(socket, sub_matches, output).await?,
+        ActionType::Skip => handle_skip(socket, sub_matches, output).await?,
+        ActionType::List => handle_list(socket, sub_matches, output).await?,
+        ActionType::Test => handle_test(socket, output).await?,
+        ActionType::History => handle_history(socket, sub_matches, output).await?,
+        ActionType::Stats => handle_stats(socket, sub_matches, output).await?,
+        ActionType::Status => handle_status(socket, sub_matches, output).await?,
+        ActionType::Subscribe => handle_subscribe(socket, output).await?,
         ActionType::Exit | ActionType::Clear => {
             info!("Exit or Clear is not supported action for unix domain client")
         }
@@ -38,16 +53,30 @@ pub async fn handle(matches: ArgMatches, socket: UnixDatagram) -> HandleUdsResul
     Ok(())
 }
 
-async fn handle_create(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUdsResult {
+async fn handle_create(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
     let (work_time, break_time) =
         util::parse_work_and_break_time(sub_matches, None).map_err(UdsHandlerError::ParseError)?;
+    // `--at` is passed as it is and resolved by the server
+    let start_in = util::parse_optional_duration_arg(sub_matches, "in")
+        .map_err(UdsHandlerError::ParseError)?;
 
     socket
         .send(
-            UdsMessage::Public(MessageRequest::Create {
-                work: work_time,
-                r#break: break_time,
-            })
+            UdsMessage::Public(
+                MessageRequest::Create {
+                    work: work_time,
+                    r#break: break_time,
+                    message: sub_matches.get_one::<String>("message").cloned(),
+                    at: sub_matches.get_one::<String>("at").cloned(),
+                    r#in: start_in,
+                    tz: get_tz_arg(sub_matches)?,
+                },
+                output,
+            )
             .encode()
             .map_err(UdsHandlerError::EncodeFailed)?
             .as_slice(),
@@ -60,17 +89,25 @@ async fn handle_create(socket: UnixDatagram, sub_matches: &ArgMatches) -> Handle
     Ok(())
 }
 
-async fn handle_queue(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUdsResult {
+async fn handle_queue(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
     let (work_time, break_time) =
         util::parse_work_and_break_time(sub_matches, None).map_err(UdsHandlerError::ParseError)?;
 
     debug!("handle_queue");
     socket
         .send(
-            UdsMessage::Public(MessageRequest::Queue {
-                work: work_time,
-                r#break: break_time,
-            })
+            UdsMessage::Public(
+                MessageRequest::Queue {
+                    work: work_time,
+                    r#break: break_time,
+                    message: sub_matches.get_one::<String>("message").cloned(),
+                },
+                output,
+            )
             .encode()
             .map_err(UdsHandlerError::EncodeFailed)?
             .as_slice(),
@@ -83,7 +120,45 @@ async fn handle_queue(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleU
     Ok(())
 }
 
-async fn handle_delete(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUdsResult {
+async fn handle_cycle(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let (work_time, break_time) =
+        util::parse_work_and_break_time(sub_matches, None).map_err(UdsHandlerError::ParseError)?;
+
+    socket
+        .send(
+            UdsMessage::Public(
+                MessageRequest::Cycle {
+                    work: work_time,
+                    r#break: break_time,
+                    long_break: util::parse_optional_duration_arg(sub_matches, "long_break")
+                        .map_err(UdsHandlerError::ParseError)?,
+                    count: util::parse_optional_arg::<u16>(sub_matches, "count")
+                        .map_err(UdsHandlerError::ParseError)?,
+                    message: sub_matches.get_one::<String>("message").cloned(),
+                },
+                output,
+            )
+            .encode()
+            .map_err(UdsHandlerError::EncodeFailed)?
+            .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_delete(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
     let (id, all) = if sub_matches.contains_id("id") {
         (
             util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?,
@@ -95,7 +170,7 @@ async fn handle_delete(socket: UnixDatagram, sub_matches: &ArgMatches) -> Handle
 
     socket
         .send(
-            UdsMessage::Public(MessageRequest::Delete { id, all })
+            UdsMessage::Public(MessageRequest::Delete { id, all }, output)
                 .encode()
                 .map_err(UdsHandlerError::EncodeFailed)?
                 .as_slice(),
@@ -108,12 +183,16 @@ async fn handle_delete(socket: UnixDatagram, sub_matches: &ArgMatches) -> Handle
     Ok(())
 }
 
-async fn handle_list(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUdsResult {
-    let show_percentage = sub_matches.get_flag("percentage");
+async fn handle_pause(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let id = util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?;
 
     socket
         .send(
-            UdsMessage::Public(MessageRequest::List { show_percentage })
+            UdsMessage::Public(MessageRequest::Pause { id }, output)
                 .encode()
                 .map_err(UdsHandlerError::EncodeFailed)?
                 .as_slice(),
@@ -126,10 +205,16 @@ async fn handle_list(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUd
     Ok(())
 }
 
-async fn handle_test(socket: UnixDatagram) -> HandleUdsResult {
+async fn handle_resume(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let id = util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?;
+
     socket
         .send(
-            UdsMessage::Public(MessageRequest::Test)
+            UdsMessage::Public(MessageRequest::Resume { id }, output)
                 .encode()
                 .map_err(UdsHandlerError::EncodeFailed)?
                 .as_slice(),
@@ -142,12 +227,18 @@ async fn handle_test(socket: UnixDatagram) -> HandleUdsResult {
     Ok(())
 }
 
-async fn handle_history(socket: UnixDatagram, sub_matches: &ArgMatches) -> HandleUdsResult {
-    let should_clear = sub_matches.get_flag("clear");
+async fn handle_extend(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let id = util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?;
+    let seconds =
+        util::parse_duration_arg(sub_matches, "minutes").map_err(UdsHandlerError::ParseError)?;
 
     socket
         .send(
-            UdsMessage::Public(MessageRequest::History { should_clear })
+            UdsMessage::Public(MessageRequest::Extend { id, seconds }, output)
                 .encode()
                 .map_err(UdsHandlerError::EncodeFailed)?
                 .as_slice(),
@@ -160,13 +251,289 @@ async fn handle_history(socket: UnixDatagram, sub_matches: &ArgMatches) -> Handl
     Ok(())
 }
 
+async fn handle_snooze(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let id = util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?;
+    let seconds =
+        util::parse_duration_arg(sub_matches, "minutes").map_err(UdsHandlerError::ParseError)?;
+
+    socket
+        .send(
+            UdsMessage::Public(MessageRequest::Snooze { id, seconds }, output)
+                .encode()
+                .map_err(UdsHandlerError::EncodeFailed)?
+                .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_skip(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let id = util::parse_arg::<u16>(sub_matches, "id").map_err(UdsHandlerError::ParseError)?;
+    let silent = sub_matches.get_flag("silent");
+
+    socket
+        .send(
+            UdsMessage::Public(MessageRequest::Skip { id, silent }, output)
+                .encode()
+                .map_err(UdsHandlerError::EncodeFailed)?
+                .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_list(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let show_percentage = sub_matches.get_flag("percentage");
+    let tz = get_tz_arg(sub_matches)?;
+
+    socket
+        .send(
+            UdsMessage::Public(
+                MessageRequest::List {
+                    show_percentage,
+                    tz,
+                },
+                output,
+            )
+            .encode()
+            .map_err(UdsHandlerError::EncodeFailed)?
+            .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_test(socket: UnixDatagram, output: OutputFormat) -> HandleUdsResult {
+    socket
+        .send(
+            UdsMessage::Public(MessageRequest::Test, output)
+                .encode()
+                .map_err(UdsHandlerError::EncodeFailed)?
+                .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_history(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let should_clear = sub_matches.get_flag("clear");
+    let tz = get_tz_arg(sub_matches)?;
+    let export = get_absolute_path_arg(sub_matches, "export")?;
+    let import = get_absolute_path_arg(sub_matches, "import")?;
+
+    socket
+        .send(
+            UdsMessage::Public(
+                MessageRequest::History {
+                    should_clear,
+                    tz,
+                    export,
+                    import,
+                    format: sub_matches.get_one::<String>("format").cloned(),
+                },
+                output,
+            )
+            .encode()
+            .map_err(UdsHandlerError::EncodeFailed)?
+            .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_stats(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let days = if sub_matches.contains_id("days") {
+        Some(util::parse_arg::<u16>(sub_matches, "days").map_err(UdsHandlerError::ParseError)?)
+    } else {
+        None
+    };
+    let tz = get_tz_arg(sub_matches)?;
+    let heatmap = sub_matches.get_flag("heatmap");
+    // ask for plain characters if the terminal printing the heatmap is not colored
+    let plain = sub_matches.get_flag("plain") || !SHOULD_COLORIZE.should_colorize();
+
+    socket
+        .send(
+            UdsMessage::Public(
+                MessageRequest::Stats {
+                    days,
+                    tz,
+                    heatmap,
+                    plain,
+                },
+                output,
+            )
+            .encode()
+            .map_err(UdsHandlerError::EncodeFailed)?
+            .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    decode_and_print_message(socket).await?;
+
+    Ok(())
+}
+
+async fn handle_status(
+    socket: UnixDatagram,
+    sub_matches: &ArgMatches,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    let format = sub_matches.get_one::<String>("format").cloned();
+
+    if !sub_matches.get_flag("watch") {
+        send_status_request(&socket, format, output).await?;
+        decode_message(&socket).await?.print();
+
+        return Ok(());
+    }
+
+    let interval = util::parse_duration_arg(sub_matches, "interval")
+        .map_err(UdsHandlerError::ParseError)?
+        .max(1);
+    // i3bar reads the blocks from an endless array after the header
+    let is_i3bar = output == OutputFormat::Text
+        && format.as_deref().map(StatusFormat::from) == Some(StatusFormat::I3bar);
+    if is_i3bar {
+        println!("{}", I3BAR_HEADER);
+    }
+
+    loop {
+        send_status_request(&socket, format.clone(), output).await?;
+        let response = decode_message(&socket).await?;
+
+        if is_i3bar {
+            response
+                .get_body()
+                .iter()
+                .for_each(|block| println!("[{}],", block));
+        } else {
+            response.print();
+        }
+
+        sleep(Duration::from_secs(u64::from(interval))).await;
+    }
+}
+
+async fn send_status_request(
+    socket: &UnixDatagram,
+    format: Option<String>,
+    output: OutputFormat,
+) -> HandleUdsResult {
+    socket
+        .send(
+            UdsMessage::Public(MessageRequest::Status { format }, output)
+                .encode()
+                .map_err(UdsHandlerError::EncodeFailed)?
+                .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    Ok(())
+}
+
+async fn handle_subscribe(socket: UnixDatagram, output: OutputFormat) -> HandleUdsResult {
+    socket
+        .send(
+            UdsMessage::Public(MessageRequest::Subscribe, output)
+                .encode()
+                .map_err(UdsHandlerError::EncodeFailed)?
+                .as_slice(),
+        )
+        .await
+        .map_err(UdsHandlerError::SocketError)?;
+
+    loop {
+        decode_message(&socket).await?.print();
+    }
+}
+
+/// get_absolute_path_arg resolves the path arg here as pomodoro may run in another directory
+fn get_absolute_path_arg(
+    sub_matches: &ArgMatches,
+    arg_name: &str,
+) -> Result<Option<String>, UdsHandlerError> {
+    let path = match sub_matches.get_one::<String>(arg_name) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let current_dir = env::current_dir().map_err(|e| {
+        UdsHandlerError::ParseError(ParseError::new(format!(
+            "failed to get the current directory: {}",
+            e
+        )))
+    })?;
+
+    Ok(Some(current_dir.join(path).to_string_lossy().to_string()))
+}
+
+/// get_tz_arg returns `--tz` after checking that it is a valid timezone
+fn get_tz_arg(sub_matches: &ArgMatches) -> Result<Option<String>, UdsHandlerError> {
+    let tz = sub_matches.get_one::<String>("tz").cloned();
+    if let Some(ref name) = tz {
+        util::parse_timezone(name).map_err(UdsHandlerError::ParseError)?;
+    }
+
+    Ok(tz)
+}
+
 async fn decode_and_print_message(socket: UnixDatagram) -> HandleUdsResult {
+    decode_message(&socket).await?.print();
+
+    Ok(())
+}
+
+async fn decode_message(socket: &UnixDatagram) -> Result<MessageResponse, UdsHandlerError> {
     let mut vec = Vec::new();
     let mut total_size = 0;
+    // the response is sent in chunks, so a buffer of a chunk is reused for every datagram
+    let mut buf = vec![0u8; ipc::CHUNK];
 
     // TODO(young): set timeout to prevent infinite loop
     loop {
-        let mut buf = vec![0u8; BUFFER_LENGTH];
         let (size, _) = socket
             .recv_from(&mut buf)
             .await
@@ -187,9 +554,5 @@ async fn decode_and_print_message(socket: UnixDatagram) -> HandleUdsResult {
 
     debug!("total_size: {}", total_size);
     let dgram = &vec.as_slice()[..total_size];
-    MessageResponse::decode(dgram)
-        .map_err(UdsHandlerError::DecodeFailed)?
-        .print();
-
-    Ok(())
+    MessageResponse::decode(dgram).map_err(UdsHandlerError::DecodeFailed)
 }
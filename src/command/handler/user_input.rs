@@ -1,9 +1,12 @@
-use chrono::Utc;
+use chrono::{Local, Utc};
 use clap::error::ErrorKind;
 use clap::{ArgMatches, Command};
+use colored::control::SHOULD_COLORIZE;
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::Path;
 use std::process;
 use std::result;
-use std::str::SplitWhitespace;
 use std::sync::Arc;
 use tabled::locator::ByColumnName;
 use tabled::object::Segment;
@@ -14,25 +17,38 @@ use tabled::{Style, TableIteratorExt};
 
 use crate::command::output::{OutputAccumulater, OutputType};
 use crate::command::util;
-use crate::command::{self, action::ActionType};
-use crate::error::UserInputHandlerError;
+use crate::command::{self, action::ActionType, DEFAULT_STATUS_FORMAT};
+use crate::error::{HistoryError, UserInputHandlerError};
+use crate::event::{self, Event, EventKind, EventSender};
+use crate::history::{self, HistoryFormat, HistoryRecord};
 use crate::notification::notify::notify_work;
-use crate::notification::{delete_notification, get_new_notification};
+use crate::notification::{
+    delete_notification, extend_notification, get_imported_notifications,
+    get_new_cycle_notifications, get_new_notification, is_behind_paused_notification,
+    pause_notification, resume_notification, skip_notification, snooze_notification, Notification,
+    NotificationRecord,
+};
+use crate::stats::{Heatmap, Statistics, DEFAULT_STATS_DAYS};
+use crate::status::{Status, StatusFormat};
 use crate::{configuration::Configuration, ArcGlue};
-use crate::{db, spawn_notification, ArcTaskMap};
+use crate::{db, spawn_notification, ArcTaskMap, InputSource, UserInput};
 
 type HandleUserInputResult = result::Result<(), UserInputHandlerError>;
 
 pub async fn handle(
-    user_input: &str,
+    user_input: &UserInput,
     id_manager: &mut u16,
     notification_task_map: &ArcTaskMap,
     glue: &ArcGlue,
     configuration: &Arc<Configuration>,
+    event_tx: &EventSender,
 ) -> Result<OutputAccumulater, UserInputHandlerError> {
     let command = command::get_main_command();
-    let input = user_input.split_whitespace();
-    let mut output_accumulator = OutputAccumulater::new();
+    let input = util::split_input(&user_input.input).map_err(UserInputHandlerError::ParseError)?;
+    let mut output_accumulator = OutputAccumulater::new(
+        user_input.output,
+        matches!(user_input.source, InputSource::StandardInput),
+    );
 
     debug!("input: {:?}", input);
 
@@ -59,6 +75,7 @@ pub async fn handle(
                 configuration,
                 notification_task_map,
                 glue,
+                event_tx,
                 id_manager,
                 &mut output_accumulator,
             )
@@ -70,6 +87,19 @@ pub async fn handle(
                 configuration,
                 notification_task_map,
                 glue,
+                event_tx,
+                id_manager,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::Cycle => {
+            handle_cycle(
+                sub_matches,
+                configuration,
+                notification_task_map,
+                glue,
+                event_tx,
                 id_manager,
                 &mut output_accumulator,
             )
@@ -80,13 +110,88 @@ pub async fn handle(
                 sub_matches,
                 notification_task_map,
                 glue,
+                event_tx,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::Pause => {
+            handle_pause(
+                sub_matches,
+                notification_task_map,
+                glue,
+                event_tx,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::Resume => {
+            handle_resume(
+                sub_matches,
+                configuration,
+                notification_task_map,
+                glue,
+                event_tx,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::Extend => {
+            handle_extend(
+                sub_matches,
+                configuration,
+                notification_task_map,
+                glue,
+                event_tx,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::Snooze => {
+            handle_snooze(
+                sub_matches,
+                configuration,
+                notification_task_map,
+                glue,
+                event_tx,
+                id_manager,
                 &mut output_accumulator,
             )
             .await?;
         }
-        ActionType::List => handle_list(sub_matches, glue, &mut output_accumulator).await?,
+        ActionType::Skip => {
+            handle_skip(
+                sub_matches,
+                configuration,
+                notification_task_map,
+                glue,
+                event_tx,
+                &mut output_accumulator,
+            )
+            .await?;
+        }
+        ActionType::List => {
+            handle_list(sub_matches, configuration, glue, &mut output_accumulator).await?
+        }
         ActionType::Test => handle_test(configuration, &mut output_accumulator).await?,
-        ActionType::History => handle_history(sub_matches, glue, &mut output_accumulator).await?,
+        ActionType::History => {
+            handle_history(
+                sub_matches,
+                configuration,
+                glue,
+                id_manager,
+                &mut output_accumulator,
+            )
+            .await?
+        }
+        ActionType::Stats => {
+            handle_stats(sub_matches, configuration, glue, &mut output_accumulator).await?
+        }
+        ActionType::Status => handle_status(sub_matches, glue, &mut output_accumulator).await?,
+        ActionType::Subscribe => output_accumulator.push(
+            OutputType::Error,
+            String::from("subscribe is only supported for the unix domain client"),
+        ),
         ActionType::Exit => process::exit(0),
         ActionType::Clear => print!("\x1B[2J\x1B[1;1H"),
     }
@@ -99,29 +204,54 @@ async fn handle_create(
     configuration: &Arc<Configuration>,
     notification_task_map: &ArcTaskMap,
     glue: &ArcGlue,
+    event_tx: &EventSender,
     id_manager: &mut u16,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
-    let notification = get_new_notification(matches, id_manager, Utc::now(), configuration.clone())
+    let timezone =
+        util::get_timezone(matches, configuration).map_err(UserInputHandlerError::ParseError)?;
+    let now = Utc::now();
+    let created_at = match timezone {
+        Some(tz) => util::parse_start_time(matches, now.with_timezone(&tz)),
+        None => util::parse_start_time(matches, now.with_timezone(&Local)),
+    }
+    .map_err(UserInputHandlerError::ParseError)?;
+    let notification = get_new_notification(matches, id_manager, created_at, configuration.clone())
         .map_err(UserInputHandlerError::NotificationError)?;
 
     let id = notification.get_id();
-    db::create_notification(glue.clone(), &notification).await;
+    db::create_notification(glue.clone(), &notification)
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    output_accumulator.set_data(notification.to_record(now));
+    event::publish(event_tx, Event::new(EventKind::Created, &notification, now));
+    event::publish_work_started(event_tx, &notification, now);
 
     let handle = spawn_notification(
         configuration.clone(),
         notification_task_map.clone(),
         glue.clone(),
+        event_tx.clone(),
         notification,
     );
 
     notification_task_map.lock().unwrap().insert(id, handle);
+
+    let starts_at = if created_at > now {
+        format!(
+            ", starts at {}",
+            util::format_timestamp(created_at, timezone)
+        )
+    } else {
+        String::new()
+    };
     output_accumulator.push(
         OutputType::Println,
         format!(
-            "[{}] Notification (id: {}) created",
+            "[{}] Notification (id: {}) created{}",
             chrono::offset::Local::now(),
-            id
+            id,
+            starts_at
         ),
     );
 
@@ -133,30 +263,61 @@ async fn handle_queue(
     configuration: &Arc<Configuration>,
     notification_task_map: &ArcTaskMap,
     glue: &ArcGlue,
+    event_tx: &EventSender,
     id_manager: &mut u16,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
-    let created_at = match db::read_last_expired_notification(glue.clone()).await {
+    let last_expired_notification = db::read_last_expired_notification(glue.clone())
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    let (created_at, queued_after) = match last_expired_notification {
         Some(n) => {
             debug!("last_expired_notification: {:?}", &n);
 
-            let (_, _, _, _, _, work_expired_at, break_expired_at) = n.get_values();
-            work_expired_at.max(break_expired_at)
+            let (id, _, _, _, _, work_expired_at, break_expired_at) = n.get_values();
+            (work_expired_at.max(break_expired_at), Some(id))
         }
-        None => Utc::now(),
+        None => (Utc::now(), None),
     };
 
     let notification = get_new_notification(matches, id_manager, created_at, configuration.clone())
-        .map_err(UserInputHandlerError::NotificationError)?;
+        .map_err(UserInputHandlerError::NotificationError)?
+        .with_queued_after(queued_after);
     let id = notification.get_id();
-    db::create_notification(glue.clone(), &notification).await;
+    db::create_notification(glue.clone(), &notification)
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    output_accumulator.set_data(notification.to_record(Utc::now()));
+    event::publish(
+        event_tx,
+        Event::new(EventKind::Created, &notification, Utc::now()),
+    );
 
+    // the task is spawned when the paused notification in front of it is resumed
+    if is_behind_paused_notification(glue.clone(), &notification)
+        .await
+        .map_err(UserInputHandlerError::NotificationError)?
+    {
+        output_accumulator.push(
+            OutputType::Println,
+            format!(
+                "[{}] Notification (id: {}) created and queued behind a paused notification",
+                chrono::offset::Local::now(),
+                id
+            ),
+        );
+
+        return Ok(());
+    }
+
+    event::publish_work_started(event_tx, &notification, Utc::now());
     notification_task_map.lock().unwrap().insert(
         id,
         spawn_notification(
             configuration.clone(),
             notification_task_map.clone(),
             glue.clone(),
+            event_tx.clone(),
             notification,
         ),
     );
@@ -172,10 +333,93 @@ async fn handle_queue(
     Ok(())
 }
 
+async fn handle_cycle(
+    matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    id_manager: &mut u16,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    // the cycle is queued after the notifications already running
+    let last_expired_notification = db::read_last_expired_notification(glue.clone())
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    let (created_at, queued_after) = match last_expired_notification {
+        Some(n) => (n.get_last_expired_at().max(Utc::now()), Some(n.get_id())),
+        None => (Utc::now(), None),
+    };
+
+    let notifications = get_new_cycle_notifications(
+        matches,
+        id_manager,
+        created_at,
+        queued_after,
+        configuration.clone(),
+    )
+    .map_err(UserInputHandlerError::NotificationError)?;
+    // every pomodoro of the cycle starts after the first one, so checking it is enough
+    let behind_paused_notification = match notifications.first() {
+        Some(n) => is_behind_paused_notification(glue.clone(), n)
+            .await
+            .map_err(UserInputHandlerError::NotificationError)?,
+        None => false,
+    };
+
+    let ids: Vec<String> = notifications
+        .iter()
+        .map(|n| n.get_id().to_string())
+        .collect();
+    let now = Utc::now();
+    output_accumulator.set_data(
+        notifications
+            .iter()
+            .map(|n| n.to_record(now))
+            .collect::<Vec<NotificationRecord>>(),
+    );
+
+    for notification in notifications {
+        let id = notification.get_id();
+        db::create_notification(glue.clone(), &notification)
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
+        event::publish(event_tx, Event::new(EventKind::Created, &notification, now));
+
+        // the tasks are spawned when the paused notification in front of them is resumed
+        if behind_paused_notification {
+            continue;
+        }
+
+        event::publish_work_started(event_tx, &notification, now);
+        let handle = spawn_notification(
+            configuration.clone(),
+            notification_task_map.clone(),
+            glue.clone(),
+            event_tx.clone(),
+            notification,
+        );
+        notification_task_map.lock().unwrap().insert(id, handle);
+    }
+
+    output_accumulator.push(
+        OutputType::Println,
+        format!(
+            "[{}] Cycle of {} notifications (id: {}) created",
+            chrono::offset::Local::now(),
+            ids.len(),
+            ids.join(", ")
+        ),
+    );
+
+    Ok(())
+}
+
 async fn handle_delete(
     sub_matches: &ArgMatches,
     notification_task_map: &ArcTaskMap,
     glue: &ArcGlue,
+    event_tx: &EventSender,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
     if sub_matches.contains_id("id") {
@@ -184,8 +428,14 @@ async fn handle_delete(
             util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
         debug!("Message::Delete called! {}", id);
 
-        match delete_notification(id, notification_task_map.clone(), glue.clone()).await {
-            Ok(_) => {
+        let result = delete_notification(id, notification_task_map.clone(), glue.clone()).await;
+        output_accumulator.set_data(get_action_data("delete", id, &result));
+        match result {
+            Ok(notification) => {
+                event::publish(
+                    event_tx,
+                    Event::new(EventKind::Deleted, &notification, Utc::now()),
+                );
                 output_accumulator.push(
                     OutputType::Println,
                     format!(
@@ -204,29 +454,269 @@ async fn handle_delete(
         // delete all
         debug!("Message:DeleteAll called!");
 
+        let mut notifications = db::list_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
         for (_, handle) in notification_task_map.lock().unwrap().iter() {
             handle.abort();
         }
-        db::delete_and_archive_all_notification(glue.clone()).await;
+        // only the time spent so far is archived
+        let now = Utc::now();
+        for notification in notifications.iter_mut() {
+            notification.cut_off(now);
+            db::update_notification(glue.clone(), notification)
+                .await
+                .map_err(UserInputHandlerError::DatabaseError)?;
+        }
+        db::delete_and_archive_all_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
+        for notification in notifications.iter() {
+            event::publish(event_tx, Event::new(EventKind::Deleted, notification, now));
+        }
         output_accumulator.push(
             OutputType::Println,
             String::from("All Notifications deleted"),
         );
+        output_accumulator.set_data(json!({ "action": "delete", "all": true, "ok": true }));
         debug!("Message::DeleteAll done");
     }
 
     Ok(())
 }
 
+async fn handle_pause(
+    sub_matches: &ArgMatches,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let id =
+        util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
+    debug!("Message::Pause called! {}", id);
+
+    let result = pause_notification(id, notification_task_map.clone(), glue.clone()).await;
+    output_accumulator.set_data(get_action_data("pause", id, &result));
+    match result {
+        Ok(notification) => {
+            event::publish(
+                event_tx,
+                Event::new(EventKind::Paused, &notification, Utc::now()),
+            );
+            output_accumulator.push(
+                OutputType::Println,
+                format!(
+                    "[{}] Notification (id: {}) paused",
+                    chrono::offset::Local::now(),
+                    id
+                ),
+            );
+        }
+        Err(e) => {
+            output_accumulator.push(OutputType::Error, format!("Error: {}", e));
+        }
+    }
+    debug!("Message::Pause done");
+
+    Ok(())
+}
+
+async fn handle_resume(
+    sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let id =
+        util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
+    debug!("Message::Resume called! {}", id);
+
+    let result = resume_notification(
+        id,
+        configuration.clone(),
+        notification_task_map.clone(),
+        glue.clone(),
+        event_tx.clone(),
+    )
+    .await;
+    output_accumulator.set_data(get_action_data("resume", id, &result));
+    match result {
+        Ok(_) => {
+            output_accumulator.push(
+                OutputType::Println,
+                format!(
+                    "[{}] Notification (id: {}) resumed",
+                    chrono::offset::Local::now(),
+                    id
+                ),
+            );
+        }
+        Err(e) => {
+            output_accumulator.push(OutputType::Error, format!("Error: {}", e));
+        }
+    }
+    debug!("Message::Resume done");
+
+    Ok(())
+}
+
+async fn handle_extend(
+    sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let id =
+        util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
+    let seconds = util::parse_duration_arg(sub_matches, "minutes")
+        .map_err(UserInputHandlerError::ParseError)?;
+    debug!("Message::Extend called! {}, {}", id, seconds);
+
+    let result = extend_notification(
+        id,
+        seconds,
+        configuration.clone(),
+        notification_task_map.clone(),
+        glue.clone(),
+        event_tx.clone(),
+    )
+    .await;
+    let mut data = get_action_data("extend", id, &result);
+    data["seconds"] = json!(seconds);
+    output_accumulator.set_data(data);
+    match result {
+        Ok(_) => {
+            output_accumulator.push(
+                OutputType::Println,
+                format!(
+                    "[{}] Notification (id: {}) extended by {}",
+                    chrono::offset::Local::now(),
+                    id,
+                    util::format_duration(seconds)
+                ),
+            );
+        }
+        Err(e) => {
+            output_accumulator.push(OutputType::Error, format!("Error: {}", e));
+        }
+    }
+    debug!("Message::Extend done");
+
+    Ok(())
+}
+
+async fn handle_snooze(
+    sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    id_manager: &mut u16,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let id =
+        util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
+    let seconds = util::parse_duration_arg(sub_matches, "minutes")
+        .map_err(UserInputHandlerError::ParseError)?;
+    debug!("Message::Snooze called! {}, {}", id, seconds);
+
+    let result = snooze_notification(
+        id,
+        seconds,
+        configuration.clone(),
+        notification_task_map.clone(),
+        glue.clone(),
+        event_tx.clone(),
+        id_manager,
+    )
+    .await;
+    let mut data = get_action_data("snooze", id, &result);
+    data["seconds"] = json!(seconds);
+    if let Ok(snoozed_id) = result {
+        data["snoozed_id"] = json!(snoozed_id);
+    }
+    output_accumulator.set_data(data);
+    match result {
+        Ok(snoozed_id) => {
+            output_accumulator.push(
+                OutputType::Println,
+                format!(
+                    "[{}] Notification (id: {}) snoozed for {}",
+                    chrono::offset::Local::now(),
+                    snoozed_id,
+                    util::format_duration(seconds)
+                ),
+            );
+        }
+        Err(e) => {
+            output_accumulator.push(OutputType::Error, format!("Error: {}", e));
+        }
+    }
+    debug!("Message::Snooze done");
+
+    Ok(())
+}
+
+async fn handle_skip(
+    sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    notification_task_map: &ArcTaskMap,
+    glue: &ArcGlue,
+    event_tx: &EventSender,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let id =
+        util::parse_arg::<u16>(sub_matches, "id").map_err(UserInputHandlerError::ParseError)?;
+    let silent = sub_matches.get_flag("silent");
+    debug!("Message::Skip called! {}", id);
+
+    let result = skip_notification(
+        id,
+        silent,
+        configuration.clone(),
+        notification_task_map.clone(),
+        glue.clone(),
+        event_tx.clone(),
+    )
+    .await;
+    output_accumulator.set_data(get_action_data("skip", id, &result));
+    match result {
+        Ok(_) => {
+            output_accumulator.push(
+                OutputType::Println,
+                format!(
+                    "[{}] Notification (id: {}) skipped the current phase",
+                    chrono::offset::Local::now(),
+                    id
+                ),
+            );
+        }
+        Err(e) => {
+            output_accumulator.push(OutputType::Error, format!("Error: {}", e));
+        }
+    }
+    debug!("Message::Skip done");
+
+    Ok(())
+}
+
 async fn handle_test(
     configuration: &Arc<Configuration>,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
     debug!("Message:NotificationTest called!");
-    let report = notify_work(&configuration.clone())
+    // a sample notification which has just finished the work
+    let notification = Notification::new(0, "", 0, 0, Utc::now());
+    let report = notify_work(&configuration.clone(), &notification)
         .await
         .map_err(UserInputHandlerError::NotificationError)?;
     output_accumulator.push(OutputType::Info, format!("\n{}", report));
+    output_accumulator.set_data(report);
 
     debug!("Message:NotificationTest done");
     output_accumulator.push(
@@ -239,14 +729,31 @@ async fn handle_test(
 
 async fn handle_list(
     sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
     glue: &ArcGlue,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
+    let timezone = util::get_timezone(sub_matches, configuration)
+        .map_err(UserInputHandlerError::ParseError)?;
+
     debug!("Message::List called!");
-    let notifications = db::list_notification(glue.clone()).await;
+    let notifications = db::list_notification(glue.clone())
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
     debug!("Message::List done");
 
-    let mut main_table = notifications.table();
+    let now = Utc::now();
+    output_accumulator.set_data(
+        notifications
+            .iter()
+            .map(|n| n.to_record(now))
+            .collect::<Vec<NotificationRecord>>(),
+    );
+
+    let mut main_table = notifications
+        .into_iter()
+        .map(|n| n.with_timezone(timezone))
+        .table();
 
     let styled_table = main_table
         .with(
@@ -272,23 +779,104 @@ async fn handle_list(
 
 async fn handle_history(
     sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
     glue: &ArcGlue,
+    id_manager: &mut u16,
     output_accumulator: &mut OutputAccumulater,
 ) -> HandleUserInputResult {
     if sub_matches.get_flag("clear") {
         debug!("Message:Clear history called!");
-        db::delete_all_archived_notification(glue.clone()).await;
+        db::delete_all_archived_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
         output_accumulator.push(
             OutputType::Println,
             String::from("All Notifications history deleted"),
         );
+        output_accumulator.set_data(json!({ "action": "clear", "ok": true }));
         debug!("Message::Clear history done");
+    } else if let Some(path) = sub_matches.get_one::<String>("export") {
+        let path = Path::new(path);
+        let format = get_history_format(sub_matches, path)?;
+
+        debug!("Message:Export history called!");
+        let archived_notifications = db::list_archived_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
+
+        let count = history::export_history(&archived_notifications, path, format, Utc::now())
+            .map_err(UserInputHandlerError::HistoryError)?;
+        output_accumulator.push(
+            OutputType::Println,
+            format!(
+                "[{}] {} notification(s) exported to {}",
+                chrono::offset::Local::now(),
+                count,
+                path.display()
+            ),
+        );
+        output_accumulator.set_data(json!({
+            "action": "export",
+            "path": path,
+            "exported": count,
+        }));
+        debug!("Message::Export history done");
+    } else if let Some(path) = sub_matches.get_one::<String>("import") {
+        let path = Path::new(path);
+        let format = get_history_format(sub_matches, path)?;
+
+        debug!("Message:Import history called!");
+        let records =
+            history::import_history(path, format).map_err(UserInputHandlerError::HistoryError)?;
+        let archived_notifications = db::list_archived_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
+
+        let total = records.len();
+        let records = history::remove_duplicates(records, &archived_notifications);
+        let notifications = get_imported_notifications(&records, id_manager)
+            .map_err(UserInputHandlerError::NotificationError)?;
+        db::create_archived_notifications(glue.clone(), &notifications)
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
+
+        output_accumulator.push(
+            OutputType::Println,
+            format!(
+                "[{}] {} notification(s) imported from {}, {} duplicate(s) skipped",
+                chrono::offset::Local::now(),
+                notifications.len(),
+                path.display(),
+                total - notifications.len()
+            ),
+        );
+        output_accumulator.set_data(json!({
+            "action": "import",
+            "path": path,
+            "imported": notifications.len(),
+            "skipped": total - notifications.len(),
+        }));
+        debug!("Message::Import history done");
     } else {
+        let timezone = util::get_timezone(sub_matches, configuration)
+            .map_err(UserInputHandlerError::ParseError)?;
+
         debug!("Message:History called!");
-        let archived_notifications = db::list_archived_notification(glue.clone()).await;
+        let archived_notifications = db::list_archived_notification(glue.clone())
+            .await
+            .map_err(UserInputHandlerError::DatabaseError)?;
         debug!("Message:History done!");
 
+        output_accumulator.set_data(
+            archived_notifications
+                .iter()
+                .map(HistoryRecord::from)
+                .collect::<Vec<HistoryRecord>>(),
+        );
+
         let table = archived_notifications
+            .into_iter()
+            .map(|n| n.with_timezone(timezone))
             .table()
             .with(
                 Style::modern()
@@ -304,10 +892,112 @@ async fn handle_history(
     Ok(())
 }
 
+async fn handle_stats(
+    sub_matches: &ArgMatches,
+    configuration: &Arc<Configuration>,
+    glue: &ArcGlue,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let timezone = util::get_timezone(sub_matches, configuration)
+        .map_err(UserInputHandlerError::ParseError)?;
+    let days = if sub_matches.contains_id("days") {
+        util::parse_arg::<u16>(sub_matches, "days").map_err(UserInputHandlerError::ParseError)?
+    } else {
+        DEFAULT_STATS_DAYS
+    };
+
+    debug!("Message:Stats called!");
+    let archived_notifications = db::list_archived_notification(glue.clone())
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    debug!("Message:Stats done!");
+
+    if sub_matches.get_flag("heatmap") {
+        let use_color = !sub_matches.get_flag("plain") && SHOULD_COLORIZE.should_colorize();
+        let heatmap = Heatmap::new(&archived_notifications, Utc::now(), timezone);
+
+        output_accumulator.push(OutputType::Info, format!("\n{}", heatmap.render(use_color)));
+        output_accumulator.set_data(heatmap.to_json());
+        output_accumulator.push(OutputType::Println, String::from("Stats succeed"));
+
+        return Ok(());
+    }
+
+    let stats = Statistics::new(&archived_notifications, Utc::now(), timezone, days);
+    output_accumulator.set_data(stats.to_json());
+
+    let tables = [
+        stats.get_total_rows().table(),
+        stats.get_daily_rows().table(),
+        stats.get_summary_rows().table(),
+    ]
+    .into_iter()
+    .map(|mut table| {
+        table
+            .with(
+                Style::modern()
+                    .off_horizontal()
+                    .horizontals([HorizontalLine::new(1, Style::modern().get_horizontal())]),
+            )
+            .with(Modify::new(Segment::all()).with(Alignment::center()))
+            .to_string()
+    })
+    .collect::<Vec<String>>();
+
+    output_accumulator.push(OutputType::Info, format!("\n{}", tables.join("\n")));
+    output_accumulator.push(OutputType::Println, String::from("Stats succeed"));
+
+    Ok(())
+}
+
+async fn handle_status(
+    sub_matches: &ArgMatches,
+    glue: &ArcGlue,
+    output_accumulator: &mut OutputAccumulater,
+) -> HandleUserInputResult {
+    let format = sub_matches
+        .get_one::<String>("format")
+        .map(|s| StatusFormat::from(s.as_str()))
+        .unwrap_or_else(|| StatusFormat::from(DEFAULT_STATUS_FORMAT));
+
+    debug!("Message::Status called!");
+    let notifications = db::list_notification(glue.clone())
+        .await
+        .map_err(UserInputHandlerError::DatabaseError)?;
+    debug!("Message::Status done");
+
+    let status = Status::new(&notifications, Utc::now());
+    output_accumulator.push(OutputType::Reply, status.render(&format));
+    output_accumulator.set_data(status);
+
+    Ok(())
+}
+
+/// get_action_data returns the result of the action on the notification, which is printed with `--output json`
+fn get_action_data<T, E: fmt::Display>(action: &str, id: u16, result: &Result<T, E>) -> Value {
+    match result {
+        Ok(_) => json!({ "action": action, "id": id, "ok": true }),
+        Err(e) => json!({ "action": action, "id": id, "ok": false, "error": e.to_string() }),
+    }
+}
+
+/// get_history_format returns `--format`, or the format matching the extension of the path.
+fn get_history_format(
+    sub_matches: &ArgMatches,
+    path: &Path,
+) -> Result<HistoryFormat, UserInputHandlerError> {
+    match sub_matches.get_one::<String>("format") {
+        Some(format) => format.parse(),
+        None => HistoryFormat::from_path(path)
+            .ok_or_else(|| HistoryError::UnknownFormat(path.to_string_lossy().to_string())),
+    }
+    .map_err(UserInputHandlerError::HistoryError)
+}
+
 // get_matches extract ArgMatches from input string
 fn get_matches(
     command: Command,
-    input: SplitWhitespace,
+    input: Vec<String>,
     output_accumulator: &mut OutputAccumulater,
 ) -> Result<Option<ArgMatches>, UserInputHandlerError> {
     match command.try_get_matches_from(input) {
@@ -1,20 +1,54 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
 pub enum OutputType {
     Info,
     Error,
     Print,
     Println,
+    // printed only if the command is typed in, otherwise it is just sent back to the uds client
+    Reply,
+}
+
+/// The format of the output sent back to the uds client
+#[derive(bincode::Encode, bincode::Decode, Clone, Copy, PartialEq, Debug, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    /// from_arg returns the format of the `--output` arg, text by default
+    pub fn from_arg(value: Option<&String>) -> Self {
+        match value.map(String::as_str) {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        }
+    }
 }
 
 pub struct OutputAccumulater {
+    format: OutputFormat,
+    is_interactive: bool,
+    printed: bool,
     body: Vec<String>,
+    data: Option<Value>,
 }
 
 impl OutputAccumulater {
-    pub fn new() -> Self {
-        OutputAccumulater { body: Vec::new() }
+    pub fn new(format: OutputFormat, is_interactive: bool) -> Self {
+        OutputAccumulater {
+            format,
+            is_interactive,
+            printed: false,
+            body: Vec::new(),
+            data: None,
+        }
     }
 
     pub fn push(&mut self, r#type: OutputType, message: String) {
+        self.printed |= !matches!(r#type, OutputType::Reply) || self.is_interactive;
+
         match r#type {
             OutputType::Info => {
                 info!("{}", message);
@@ -28,13 +62,106 @@ impl OutputAccumulater {
             OutputType::Println => {
                 println!("{}", message);
             }
+            OutputType::Reply => {
+                if self.is_interactive {
+                    println!("{}", message);
+                }
+            }
         }
 
         self.body.push(message);
     }
 
+    /// has_printed returns whether any message is printed to the terminal
+    pub fn has_printed(&self) -> bool {
+        self.printed
+    }
+
+    /// set_data keeps the structured result of the command, which is sent instead of
+    /// the messages if the output format is json
+    pub fn set_data<T: Serialize>(&mut self, data: T) {
+        let data = serde_json::to_value(data)
+            .unwrap_or_else(|e| json!({ "error": format!("failed to serialize: {}", e) }));
+
+        self.data = Some(data);
+    }
+
     // take_body extract messages. After call, OutputAccumulater has empty body
+    // In json format, the body is a json document of the data,
+    // or of the messages if the command has no structured result
     pub fn take_body(&mut self) -> Vec<String> {
-        std::mem::take(&mut self.body)
+        let body = std::mem::take(&mut self.body);
+
+        match self.format {
+            OutputFormat::Text => body,
+            OutputFormat::Json => {
+                let data = self
+                    .data
+                    .take()
+                    .unwrap_or_else(|| json!({ "messages": body }));
+
+                vec![data.to_string()]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{OutputAccumulater, OutputFormat, OutputType};
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!(OutputFormat::Text, OutputFormat::from_arg(None));
+        assert_eq!(
+            OutputFormat::Text,
+            OutputFormat::from_arg(Some(&String::from("text")))
+        );
+        assert_eq!(
+            OutputFormat::Json,
+            OutputFormat::from_arg(Some(&String::from("json")))
+        );
+    }
+
+    #[test]
+    fn test_reply() {
+        let mut output = OutputAccumulater::new(OutputFormat::Text, false);
+        output.push(OutputType::Reply, String::from("work 12:34 review PR"));
+        assert!(!output.has_printed());
+        assert_eq!(vec!["work 12:34 review PR"], output.take_body());
+
+        let mut output = OutputAccumulater::new(OutputFormat::Text, true);
+        output.push(OutputType::Reply, String::from("work 12:34 review PR"));
+        assert!(output.has_printed());
+    }
+
+    #[test]
+    fn test_take_body() {
+        let mut output = OutputAccumulater::new(OutputFormat::Text, true);
+        output.push(
+            OutputType::Println,
+            String::from("Notification (id: 1) created"),
+        );
+        output.set_data(json!({ "id": 1 }));
+        assert_eq!(vec!["Notification (id: 1) created"], output.take_body());
+        assert!(output.take_body().is_empty());
+
+        let mut output = OutputAccumulater::new(OutputFormat::Json, false);
+        output.push(
+            OutputType::Println,
+            String::from("Notification (id: 1) created"),
+        );
+        output.set_data(json!({ "id": 1 }));
+        assert_eq!(vec![r#"{"id":1}"#], output.take_body());
+
+        assert!(output.has_printed());
+
+        output.push(OutputType::Error, String::from("Error: not found"));
+        assert_eq!(
+            vec![r#"{"messages":["Error: not found"]}"#],
+            output.take_body()
+        );
     }
 }
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
 use clap::ArgMatches;
 use clap_complete::Shell;
 use std::io::{self, Write};
@@ -8,45 +10,246 @@ use crate::command::{DEFAULT_BREAK_TIME, DEFAULT_WORK_TIME};
 use crate::configuration::Configuration;
 use crate::error::ParseError;
 
+/// parse_work_and_break_time returns the work and break time in seconds.
+/// Default values of the configuration, which are in minutes, are used if the args are not given.
 pub fn parse_work_and_break_time(
     matches: &ArgMatches,
     configuration: Option<&Arc<Configuration>>,
-) -> Result<(Option<u16>, Option<u16>), ParseError> {
+) -> Result<(Option<u32>, Option<u32>), ParseError> {
+    let work_time = parse_optional_duration_arg(matches, "work")?;
+    let break_time = parse_optional_duration_arg(matches, "break")?;
+
     if let Some(conf) = configuration {
-        let mut work_time = match conf.get_work_time() {
-            Some(work_time) => work_time,
-            None => DEFAULT_WORK_TIME,
-        };
+        let work_time = work_time
+            .unwrap_or_else(|| u32::from(conf.get_work_time().unwrap_or(DEFAULT_WORK_TIME)) * 60);
 
-        let mut break_time = match conf.get_break_time() {
-            Some(break_time) => break_time,
-            None => DEFAULT_BREAK_TIME,
-        };
+        let break_time = break_time
+            .unwrap_or_else(|| u32::from(conf.get_break_time().unwrap_or(DEFAULT_BREAK_TIME)) * 60);
 
-        if let Ok(val) = parse_arg::<u16>(matches, "work") {
-            work_time = val;
-        };
+        Ok((Some(work_time), Some(break_time)))
+    } else {
+        Ok((work_time, break_time))
+    }
+}
 
-        if let Ok(val) = parse_arg::<u16>(matches, "break") {
-            break_time = val;
-        };
+/// parse_duration_arg parses the arg as a duration in seconds. See `parse_duration`.
+pub fn parse_duration_arg(arg_matches: &ArgMatches, arg_name: &str) -> Result<u32, ParseError> {
+    let str = arg_matches
+        .get_one::<String>(arg_name)
+        .ok_or(format!("failed to get ({}) from cli", arg_name))
+        .map_err(ParseError::new)?;
 
-        Ok((Some(work_time), Some(break_time)))
+    parse_duration(str)
+}
+
+/// parse_optional_duration_arg is same as `parse_duration_arg` but returns None if the arg is not given.
+pub fn parse_optional_duration_arg(
+    arg_matches: &ArgMatches,
+    arg_name: &str,
+) -> Result<Option<u32>, ParseError> {
+    arg_matches
+        .get_one::<String>(arg_name)
+        .map(|s| parse_duration(s))
+        .transpose()
+}
+
+/// parse_duration parses a duration like `25m`, `1h30m`, `45s` or `1.5h` into seconds.
+/// A number without unit is considered as minutes, e.g. `25` is 25 minutes.
+pub fn parse_duration(input: &str) -> Result<u32, ParseError> {
+    parse_duration_with_default_unit(input, 60.0)
+}
+
+/// parse_seconds parses a duration like `parse_duration`, but a number without unit
+/// is considered as seconds, e.g. `1500` is 25 minutes, as in the exported history.
+pub fn parse_seconds(input: &str) -> Result<u32, ParseError> {
+    parse_duration_with_default_unit(input, 1.0)
+}
+
+fn parse_duration_with_default_unit(input: &str, default_unit: f64) -> Result<u32, ParseError> {
+    let input = input.trim();
+    let invalid =
+        |reason: String| ParseError::new(format!("invalid duration ({}): {}", input, reason));
+
+    if input.is_empty() {
+        return Err(invalid(String::from("duration is empty")));
+    }
+
+    // only plain numbers like `25` or `0.5`, so that a sign or an exponent is rejected
+    let seconds = if is_plain_number(input) {
+        let value = input
+            .parse::<f64>()
+            .map_err(|_| invalid(format!("'{}' is not a number", input)))?;
+        value * default_unit
     } else {
-        let mut work_time = None;
-        let mut break_time = None;
+        let mut seconds = 0.0;
+        let mut number = String::new();
+
+        for c in input.chars() {
+            if c.is_ascii_digit() || c == '.' {
+                number.push(c);
+                continue;
+            }
+
+            let unit = match c {
+                'h' => 3600.0,
+                'm' => 60.0,
+                's' => 1.0,
+                _ => return Err(invalid(format!("unknown unit '{}', use h, m or s", c))),
+            };
 
-        if let Ok(val) = parse_arg::<u16>(matches, "work") {
-            work_time = Some(val);
+            if number.is_empty() {
+                return Err(invalid(format!("expected a number before '{}'", c)));
+            }
+
+            let value = number
+                .parse::<f64>()
+                .map_err(|_| invalid(format!("'{}' is not a number", number)))?;
+            seconds += value * unit;
+            number.clear();
         }
 
-        if let Ok(val) = parse_arg::<u16>(matches, "break") {
-            break_time = Some(val);
+        if !number.is_empty() {
+            return Err(invalid(format!("missing unit after '{}'", number)));
         }
-        Ok((work_time, break_time))
+
+        seconds
+    };
+
+    if !seconds.is_finite() || seconds < 0.0 || seconds > f64::from(u32::MAX) {
+        return Err(invalid(String::from("duration is out of range")));
+    }
+
+    Ok(seconds.round() as u32)
+}
+
+/// is_plain_number checks whether the input is made of digits with an optional fraction
+fn is_plain_number(input: &str) -> bool {
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    match input.split_once('.') {
+        Some((integer, fraction)) => is_digits(integer) && is_digits(fraction),
+        None => is_digits(input),
+    }
+}
+
+/// format_duration formats seconds like `1h30m`, `25m` or `45s`.
+pub fn format_duration(seconds: u32) -> String {
+    if seconds == 0 {
+        return String::from("0s");
+    }
+
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    let mut formatted = String::new();
+
+    if hours > 0 {
+        formatted.push_str(&format!("{}h", hours));
+    }
+
+    if minutes > 0 {
+        formatted.push_str(&format!("{}m", minutes));
+    }
+
+    if seconds > 0 {
+        formatted.push_str(&format!("{}s", seconds));
+    }
+
+    formatted
+}
+
+/// parse_start_time returns when the notification starts, which is given by `--at` or `--in`.
+/// `now` is returned if neither of them is given.
+pub fn parse_start_time<Tz: TimeZone>(
+    matches: &ArgMatches,
+    now: DateTime<Tz>,
+) -> Result<DateTime<Utc>, ParseError> {
+    if let Some(seconds) = parse_optional_duration_arg(matches, "in")? {
+        return Ok(now.with_timezone(&Utc) + Duration::seconds(i64::from(seconds)));
+    }
+
+    match matches.get_one::<String>("at") {
+        Some(at) => parse_wall_clock_time(at, now),
+        None => Ok(now.with_timezone(&Utc)),
+    }
+}
+
+/// parse_wall_clock_time parses a time like `14:00` or `2026-10-18 09:30` in the timezone of `now`.
+/// A time without date is the next one to come, so it can be tomorrow.
+pub fn parse_wall_clock_time<Tz: TimeZone>(
+    input: &str,
+    now: DateTime<Tz>,
+) -> Result<DateTime<Utc>, ParseError> {
+    let input = input.trim();
+    let invalid = |reason: &str| ParseError::new(format!("invalid time ({}): {}", input, reason));
+
+    let time = NaiveTime::parse_from_str(input, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(input, "%H:%M:%S"));
+
+    let (naive, is_time_only) = match time {
+        Ok(time) => (now.date_naive().and_time(time), true),
+        Err(_) => {
+            let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
+                .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S"))
+                .map_err(|_| invalid("use HH:MM or YYYY-MM-DD HH:MM"))?;
+
+            (naive, false)
+        }
+    };
+
+    let to_utc = |naive: NaiveDateTime| {
+        now.timezone()
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+            .ok_or_else(|| invalid("the time does not exist in the timezone"))
+    };
+
+    let start_at = to_utc(naive)?;
+    if start_at > now {
+        return Ok(start_at);
+    }
+
+    if is_time_only {
+        to_utc(naive + Duration::days(1))
+    } else {
+        Err(invalid("the time is already passed"))
+    }
+}
+
+/// parse_timezone parses an IANA timezone name like `Asia/Seoul`.
+pub fn parse_timezone(name: &str) -> Result<Tz, ParseError> {
+    name.parse::<Tz>().map_err(|_| {
+        ParseError::new(format!(
+            "invalid timezone ({}): use an IANA name like Asia/Seoul",
+            name
+        ))
+    })
+}
+
+/// get_timezone returns the timezone given by `--tz`, or the one in the configuration.
+/// None means the local timezone.
+pub fn get_timezone(
+    matches: &ArgMatches,
+    configuration: &Configuration,
+) -> Result<Option<Tz>, ParseError> {
+    match matches.get_one::<String>("tz") {
+        Some(name) => parse_timezone(name).map(Some),
+        None => Ok(configuration.get_timezone()),
     }
 }
 
+/// format_timestamp formats the time in the timezone, or in the local timezone if it's None.
+pub fn format_timestamp(time: DateTime<Utc>, timezone: Option<Tz>) -> String {
+    match timezone {
+        Some(tz) => time.with_timezone(&tz).format("%F %T %z").to_string(),
+        None => time.with_timezone(&Local).format("%F %T %z").to_string(),
+    }
+}
+
+/// to_rfc3339 formats the time in RFC 3339 with seconds, which is used for the machine-readable output.
+pub fn to_rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
 pub fn parse_shell(matches: &ArgMatches) -> Option<Shell> {
     let shell = matches.get_one::<String>("shell");
     if let Some(shell) = shell {
@@ -80,6 +283,77 @@ where
     Ok(parsed)
 }
 
+/// parse_optional_arg is same as `parse_arg` but returns None if the arg is not given.
+pub fn parse_optional_arg<C>(
+    arg_matches: &ArgMatches,
+    arg_name: &str,
+) -> Result<Option<C>, ParseError>
+where
+    C: FromStr,
+{
+    if arg_matches.contains_id(arg_name) {
+        parse_arg(arg_matches, arg_name).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// split_input splits the input into arguments like a shell does,
+/// so that an argument containing whitespaces can be passed using quotes.
+/// e.g. `create -m "review PR #42"` is split into `create`, `-m` and `review PR #42`
+pub fn split_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => arg.push(c),
+            (_, '\\') => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| ParseError::new(String::from("input ends with escape")))?;
+                arg.push(escaped);
+                in_arg = true;
+            }
+            (Some('"'), '"') => quote = None,
+            (Some(_), _) => arg.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut arg));
+                    in_arg = false;
+                }
+            }
+            (None, _) => {
+                arg.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(ParseError::new(format!("unclosed quote ({}) in input", q)));
+    }
+
+    if in_arg {
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+/// quote_arg quotes the argument so that `split_input` returns it as a single argument
+pub fn quote_arg(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 pub fn print_start_up() {
     let stdout = &mut io::stdout();
     write!(stdout, "> ").unwrap();
@@ -96,9 +370,13 @@ where
 
 #[cfg(test)]
 mod tests {
+    use chrono::{FixedOffset, TimeZone, Utc};
     use clap::{Arg, Command};
 
-    use super::parse_arg;
+    use super::{
+        format_duration, format_timestamp, parse_arg, parse_duration, parse_seconds,
+        parse_start_time, parse_timezone, parse_wall_clock_time, quote_arg, split_input,
+    };
 
     #[test]
     fn test_parse_arg() {
@@ -123,4 +401,155 @@ mod tests {
             .to_string()
             .contains("failed to parse arg"));
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(25 * 60, parse_duration("25").unwrap());
+        assert_eq!(25 * 60, parse_duration("25m").unwrap());
+        assert_eq!(90 * 60, parse_duration("1h30m").unwrap());
+        assert_eq!(45, parse_duration("45s").unwrap());
+        assert_eq!(90 * 60, parse_duration("1.5h").unwrap());
+        assert_eq!(30, parse_duration("0.5").unwrap());
+        assert_eq!(3600 + 5, parse_duration("1h5s").unwrap());
+        assert_eq!(0, parse_duration("0").unwrap());
+
+        assert_eq!(1500, parse_seconds("1500").unwrap());
+        assert_eq!(1500, parse_seconds("1500.0").unwrap());
+        assert_eq!(25 * 60, parse_seconds("25m").unwrap());
+        assert!(parse_seconds("1e3").is_err());
+
+        let error_cases = vec![
+            ("", "duration is empty"),
+            ("25x", "unknown unit 'x'"),
+            ("h", "expected a number before 'h'"),
+            ("1h30", "missing unit after '30'"),
+            ("1.2.3m", "'1.2.3' is not a number"),
+            ("-5m", "unknown unit '-'"),
+            ("+5", "unknown unit '+'"),
+            ("1e3", "unknown unit 'e'"),
+            ("1e-9", "unknown unit 'e'"),
+            ("inf", "unknown unit 'i'"),
+            (".5", "missing unit after '.5'"),
+        ];
+
+        for (input, expected) in error_cases {
+            let message = parse_duration(input).unwrap_err().to_string();
+            assert!(
+                message.contains(expected),
+                "input: {}, message: {}",
+                input,
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("0s", format_duration(0));
+        assert_eq!("45s", format_duration(45));
+        assert_eq!("25m", format_duration(25 * 60));
+        assert_eq!("1h30m", format_duration(90 * 60));
+        assert_eq!("1h5s", format_duration(3605));
+    }
+
+    #[test]
+    fn test_parse_wall_clock_time() {
+        let tz = FixedOffset::east(9 * 3600);
+        let now = tz.ymd(2026, 10, 17).and_hms(13, 0, 0);
+
+        // later today
+        let start_at = parse_wall_clock_time("14:00", now).unwrap();
+        assert_eq!(tz.ymd(2026, 10, 17).and_hms(14, 0, 0), start_at);
+
+        // already passed today, so tomorrow
+        let start_at = parse_wall_clock_time("09:30:15", now).unwrap();
+        assert_eq!(tz.ymd(2026, 10, 18).and_hms(9, 30, 15), start_at);
+
+        let start_at = parse_wall_clock_time("2026-10-18 09:30", now).unwrap();
+        assert_eq!(Utc.ymd(2026, 10, 18).and_hms(0, 30, 0), start_at);
+
+        let result = parse_wall_clock_time("2026-10-16 09:30", now);
+        assert!(result.err().unwrap().to_string().contains("already passed"));
+
+        let result = parse_wall_clock_time("tomorrow", now);
+        assert!(result.err().unwrap().to_string().contains("use HH:MM"));
+    }
+
+    #[test]
+    fn test_timezone() {
+        let tz = parse_timezone("America/New_York").unwrap();
+        assert!(parse_timezone("Mars/Olympus").is_err());
+
+        // daylight saving time is applied
+        let summer = Utc.ymd(2026, 7, 1).and_hms(12, 0, 0);
+        assert_eq!(
+            "2026-07-01 08:00:00 -0400",
+            format_timestamp(summer, Some(tz))
+        );
+        let winter = Utc.ymd(2026, 12, 1).and_hms(12, 0, 0);
+        assert_eq!(
+            "2026-12-01 07:00:00 -0500",
+            format_timestamp(winter, Some(tz))
+        );
+
+        // wall clock time is read in the timezone
+        let now = Utc.ymd(2026, 7, 1).and_hms(12, 0, 0).with_timezone(&tz);
+        assert_eq!(
+            Utc.ymd(2026, 7, 1).and_hms(13, 0, 0),
+            parse_wall_clock_time("09:00", now).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_start_time() {
+        let cmd = Command::new("myapp")
+            .arg(Arg::new("at").long("at").num_args(1))
+            .arg(Arg::new("in").long("in").num_args(1));
+        let now = Utc.ymd(2026, 10, 17).and_hms(13, 0, 0);
+
+        let m = cmd.clone().get_matches_from(vec!["myapp"]);
+        assert_eq!(now, parse_start_time(&m, now).unwrap());
+
+        let m = cmd.clone().get_matches_from(vec!["myapp", "--in", "20m"]);
+        assert_eq!(
+            Utc.ymd(2026, 10, 17).and_hms(13, 20, 0),
+            parse_start_time(&m, now).unwrap()
+        );
+
+        let m = cmd.clone().get_matches_from(vec!["myapp", "--at", "14:00"]);
+        assert_eq!(
+            Utc.ymd(2026, 10, 17).and_hms(14, 0, 0),
+            parse_start_time(&m, now).unwrap()
+        );
+
+        let m = cmd.get_matches_from(vec!["myapp", "--in", "20x"]);
+        assert!(parse_start_time(&m, now).is_err());
+    }
+
+    #[test]
+    fn test_split_input() {
+        let args = split_input("create  -w 25 -m \"review PR #42\"").unwrap();
+        assert_eq!(vec!["create", "-w", "25", "-m", "review PR #42"], args);
+
+        let args = split_input("create -m 'it\"s' -m it\\'s -m \"\"").unwrap();
+        assert_eq!(vec!["create", "-m", "it\"s", "-m", "it's", "-m", ""], args);
+
+        let args = split_input("   ").unwrap();
+        assert!(args.is_empty());
+
+        let result = split_input("create -m \"review");
+        assert!(result.is_err());
+        assert!(result.err().unwrap().to_string().contains("unclosed quote"));
+    }
+
+    #[test]
+    fn test_quote_arg() {
+        ["review PR #42", "say \"hi\"", "back\\slash", "", "it's"]
+            .into_iter()
+            .for_each(|arg| {
+                let input = format!("create -m {}", quote_arg(arg));
+                let args = split_input(&input).unwrap();
+                assert_eq!(vec!["create", "-m", arg], args);
+            });
+    }
 }
@@ -1,5 +1,8 @@
+use chrono_tz::Tz;
 use clap::ArgMatches;
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsStr;
 use std::fs::File;
@@ -11,6 +14,8 @@ use crate::error::ConfigurationError;
 use crate::report::generate_configuration_report;
 
 pub const SLACK_API_URL: &str = "https://slack.com/api/chat.postMessage";
+pub const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+pub const NTFY_URL: &str = "https://ntfy.sh";
 
 #[derive(Deserialize, Debug, Default, Clone)]
 pub struct Configuration {
@@ -18,8 +23,24 @@ pub struct Configuration {
     slack_configuration: Option<SlackConfiguration>,
     #[serde(rename(deserialize = "discord"))]
     discord_configuration: Option<DiscordConfiguration>,
+    #[serde(rename(deserialize = "telegram"))]
+    telegram_configuration: Option<TelegramConfiguration>,
+    #[serde(rename(deserialize = "matrix"))]
+    matrix_configuration: Option<MatrixConfiguration>,
+    #[serde(rename(deserialize = "ntfy"))]
+    ntfy_configuration: Option<NtfyConfiguration>,
+    #[serde(rename(deserialize = "gotify"))]
+    gotify_configuration: Option<GotifyConfiguration>,
+    #[serde(rename(deserialize = "storage"))]
+    storage_configuration: Option<StorageConfiguration>,
+    #[serde(default)]
+    webhooks: Vec<WebhookConfiguration>,
+    missed_notification: Option<MissedNotificationPolicy>,
     work_time_default_value: Option<u16>,
     break_time_default_value: Option<u16>,
+    long_break_time_default_value: Option<u16>,
+    long_break_interval: Option<u16>,
+    timezone: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Default, Clone)]
@@ -33,6 +54,90 @@ struct DiscordConfiguration {
     webhook_url: Option<String>,
 }
 
+#[derive(Deserialize, Debug, Default, Clone)]
+struct TelegramConfiguration {
+    bot_token: Option<String>,
+    chat_id: Option<TelegramChatId>,
+    // TELEGRAM_API_URL if not present
+    api_url: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+struct MatrixConfiguration {
+    homeserver_url: Option<String>,
+    access_token: Option<String>,
+    room_id: Option<String>,
+}
+
+/// The topic of ntfy. The token is used for the access token, otherwise the username and the password are used.
+#[derive(Deserialize, Debug, Default, Clone)]
+struct NtfyConfiguration {
+    // NTFY_URL if not present
+    server_url: Option<String>,
+    topic: Option<String>,
+    token: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    // 1 (min) to 5 (max), 3 (default) if not present
+    priority: Option<u8>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// The application of the gotify server
+#[derive(Deserialize, Debug, Default, Clone)]
+struct GotifyConfiguration {
+    server_url: Option<String>,
+    app_token: Option<String>,
+    // 0 to 10, 5 (makes a sound) if not present
+    priority: Option<u8>,
+}
+
+/// The chat id is either a number, e.g. `-1001234567890`, or the username of the channel, e.g. `@pomodoro`
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum TelegramChatId {
+    Id(i64),
+    Username(String),
+}
+
+/// An outgoing webhook. The placeholders like `{{description}}` in the body are replaced
+/// with the values of the notification. The body is either a string or a json value.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfiguration {
+    pub url: String,
+    // POST if not present
+    pub method: Option<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<Value>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+struct StorageConfiguration {
+    #[serde(rename(deserialize = "type"))]
+    storage_type: Option<StorageType>,
+    path: Option<String>,
+}
+
+/// The storage used by the database. `Memory` loses every notification on exit,
+/// `Sled` persists notifications and history to the given path.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageType {
+    Memory,
+    Sled,
+}
+
+/// What to do with a notification which expired while pomodoro was not running.
+/// `Fire` sends the missed notification on start up, `Archive` silently archives it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MissedNotificationPolicy {
+    Fire,
+    Archive,
+}
+
 impl Configuration {
     pub fn get_slack_token(&self) -> &Option<String> {
         match &self.slack_configuration {
@@ -55,6 +160,154 @@ impl Configuration {
         }
     }
 
+    pub fn get_telegram_bot_token(&self) -> &Option<String> {
+        match &self.telegram_configuration {
+            Some(config) => &config.bot_token,
+            None => &None,
+        }
+    }
+
+    pub fn get_telegram_chat_id(&self) -> Option<String> {
+        match &self.telegram_configuration {
+            Some(TelegramConfiguration {
+                chat_id: Some(chat_id),
+                ..
+            }) => match chat_id {
+                TelegramChatId::Id(id) => Some(id.to_string()),
+                TelegramChatId::Username(username) => Some(username.clone()),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn get_telegram_api_url(&self) -> &str {
+        match &self.telegram_configuration {
+            Some(TelegramConfiguration {
+                api_url: Some(api_url),
+                ..
+            }) => api_url,
+            _ => TELEGRAM_API_URL,
+        }
+    }
+
+    pub fn get_matrix_homeserver_url(&self) -> &Option<String> {
+        match &self.matrix_configuration {
+            Some(config) => &config.homeserver_url,
+            None => &None,
+        }
+    }
+
+    pub fn get_matrix_access_token(&self) -> &Option<String> {
+        match &self.matrix_configuration {
+            Some(config) => &config.access_token,
+            None => &None,
+        }
+    }
+
+    pub fn get_matrix_room_id(&self) -> &Option<String> {
+        match &self.matrix_configuration {
+            Some(config) => &config.room_id,
+            None => &None,
+        }
+    }
+
+    pub fn get_ntfy_server_url(&self) -> &str {
+        match &self.ntfy_configuration {
+            Some(NtfyConfiguration {
+                server_url: Some(server_url),
+                ..
+            }) => server_url,
+            _ => NTFY_URL,
+        }
+    }
+
+    pub fn get_ntfy_topic(&self) -> &Option<String> {
+        match &self.ntfy_configuration {
+            Some(config) => &config.topic,
+            None => &None,
+        }
+    }
+
+    pub fn get_ntfy_token(&self) -> &Option<String> {
+        match &self.ntfy_configuration {
+            Some(config) => &config.token,
+            None => &None,
+        }
+    }
+
+    pub fn get_ntfy_username(&self) -> &Option<String> {
+        match &self.ntfy_configuration {
+            Some(config) => &config.username,
+            None => &None,
+        }
+    }
+
+    pub fn get_ntfy_password(&self) -> &Option<String> {
+        match &self.ntfy_configuration {
+            Some(config) => &config.password,
+            None => &None,
+        }
+    }
+
+    pub fn get_ntfy_priority(&self) -> Option<u8> {
+        self.ntfy_configuration
+            .as_ref()
+            .and_then(|config| config.priority)
+    }
+
+    pub fn get_ntfy_tags(&self) -> &[String] {
+        match &self.ntfy_configuration {
+            Some(config) => &config.tags,
+            None => &[],
+        }
+    }
+
+    pub fn get_gotify_server_url(&self) -> &Option<String> {
+        match &self.gotify_configuration {
+            Some(config) => &config.server_url,
+            None => &None,
+        }
+    }
+
+    pub fn get_gotify_app_token(&self) -> &Option<String> {
+        match &self.gotify_configuration {
+            Some(config) => &config.app_token,
+            None => &None,
+        }
+    }
+
+    pub fn get_gotify_priority(&self) -> Option<u8> {
+        self.gotify_configuration
+            .as_ref()
+            .and_then(|config| config.priority)
+    }
+
+    pub fn get_webhooks(&self) -> &Vec<WebhookConfiguration> {
+        &self.webhooks
+    }
+
+    pub fn get_storage_type(&self) -> StorageType {
+        match &self.storage_configuration {
+            Some(StorageConfiguration {
+                storage_type: Some(storage_type),
+                ..
+            }) => *storage_type,
+            _ => StorageType::Memory,
+        }
+    }
+
+    pub fn get_storage_path(&self) -> &Option<String> {
+        match &self.storage_configuration {
+            Some(config) => &config.path,
+            None => &None,
+        }
+    }
+
+    pub fn get_missed_notification_policy(&self) -> MissedNotificationPolicy {
+        self.missed_notification
+            .unwrap_or(MissedNotificationPolicy::Archive)
+    }
+
     pub fn get_work_time(&self) -> Option<u16> {
         self.work_time_default_value
     }
@@ -62,6 +315,24 @@ impl Configuration {
     pub fn get_break_time(&self) -> Option<u16> {
         self.break_time_default_value
     }
+
+    pub fn get_long_break_time(&self) -> Option<u16> {
+        self.long_break_time_default_value
+    }
+
+    pub fn get_long_break_interval(&self) -> Option<u16> {
+        self.long_break_interval
+    }
+
+    pub fn get_timezone_name(&self) -> &Option<String> {
+        &self.timezone
+    }
+
+    /// get_timezone returns the timezone to show and schedule the notifications in.
+    /// None means the local timezone, which is also used if the name is not a valid IANA timezone.
+    pub fn get_timezone(&self) -> Option<Tz> {
+        self.timezone.as_ref().and_then(|name| name.parse().ok())
+    }
 }
 
 pub fn get_configuration(matches: &ArgMatches) -> Result<Arc<Configuration>, ConfigurationError> {
@@ -112,9 +383,13 @@ fn get_configuration_from_file<P: AsRef<Path> + AsRef<OsStr>>(
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
     use std::path::PathBuf;
 
-    use super::load_configuration;
+    use super::{
+        load_configuration, Configuration, MissedNotificationPolicy, StorageType, NTFY_URL,
+        TELEGRAM_API_URL,
+    };
 
     #[test]
     fn test_initialize_configuration_some() {
@@ -137,6 +412,60 @@ mod tests {
         assert_eq!(true, discord_webhook_url.is_some());
         assert!(discord_webhook_url.as_ref().unwrap().eq("your-webhook-url"));
 
+        assert_eq!(
+            &Some(String::from("your-bot-token")),
+            config.get_telegram_bot_token()
+        );
+        assert_eq!(
+            Some(String::from("-1001234567890")),
+            config.get_telegram_chat_id()
+        );
+        assert_eq!(TELEGRAM_API_URL, config.get_telegram_api_url());
+
+        assert_eq!(
+            &Some(String::from("https://matrix.example.org")),
+            config.get_matrix_homeserver_url()
+        );
+        assert_eq!(
+            &Some(String::from("your-access-token")),
+            config.get_matrix_access_token()
+        );
+        assert_eq!(
+            &Some(String::from("!your-room-id:example.org")),
+            config.get_matrix_room_id()
+        );
+
+        assert_eq!(NTFY_URL, config.get_ntfy_server_url());
+        assert_eq!(&Some(String::from("your-topic")), config.get_ntfy_topic());
+        assert_eq!(
+            &Some(String::from("your-access-token")),
+            config.get_ntfy_token()
+        );
+        assert!(config.get_ntfy_username().is_none());
+        assert!(config.get_ntfy_password().is_none());
+        assert_eq!(Some(4), config.get_ntfy_priority());
+        assert_eq!([String::from("tomato")], config.get_ntfy_tags());
+
+        assert_eq!(
+            &Some(String::from("https://gotify.example.org")),
+            config.get_gotify_server_url()
+        );
+        assert_eq!(
+            &Some(String::from("your-app-token")),
+            config.get_gotify_app_token()
+        );
+        assert_eq!(None, config.get_gotify_priority());
+
+        assert_eq!(StorageType::Sled, config.get_storage_type());
+        let storage_path = config.get_storage_path();
+        assert!(storage_path.is_some());
+        assert!(storage_path.as_ref().unwrap().eq("pomodoro.db"));
+
+        assert_eq!(
+            MissedNotificationPolicy::Fire,
+            config.get_missed_notification_policy()
+        );
+
         let work_time = config.get_work_time();
         assert_eq!(true, work_time.is_some());
         assert_eq!(work_time.unwrap(), 30);
@@ -144,6 +473,23 @@ mod tests {
         let break_time = config.get_break_time();
         assert_eq!(true, break_time.is_some());
         assert_eq!(break_time.unwrap(), 10);
+
+        assert_eq!(Some(20), config.get_long_break_time());
+        assert_eq!(Some(3), config.get_long_break_interval());
+        assert_eq!(Some(chrono_tz::Asia::Seoul), config.get_timezone());
+
+        let webhooks = config.get_webhooks();
+        assert_eq!(1, webhooks.len());
+        assert_eq!("https://chat.example.com/hooks/pomodoro", webhooks[0].url);
+        assert_eq!(Some(String::from("POST")), webhooks[0].method);
+        assert_eq!(
+            Some(&String::from("Bearer your-token")),
+            webhooks[0].headers.get("Authorization")
+        );
+        assert_eq!(
+            Some(json!({ "text": "{{event}}: {{description}}" })),
+            webhooks[0].body
+        );
     }
 
     #[test]
@@ -164,11 +510,57 @@ mod tests {
                 let discord_webhook_url = config.get_discord_webhook_url();
                 assert_eq!(true, discord_webhook_url.is_none());
 
+                assert!(config.get_telegram_bot_token().is_none());
+                assert!(config.get_telegram_chat_id().is_none());
+                assert_eq!(TELEGRAM_API_URL, config.get_telegram_api_url());
+
+                assert!(config.get_matrix_homeserver_url().is_none());
+                assert!(config.get_matrix_access_token().is_none());
+                assert!(config.get_matrix_room_id().is_none());
+                assert_eq!(NTFY_URL, config.get_ntfy_server_url());
+                assert!(config.get_ntfy_topic().is_none());
+                assert!(config.get_ntfy_token().is_none());
+                assert!(config.get_ntfy_priority().is_none());
+                assert!(config.get_ntfy_tags().is_empty());
+                assert!(config.get_gotify_server_url().is_none());
+                assert!(config.get_gotify_app_token().is_none());
+                assert!(config.get_gotify_priority().is_none());
+
+                assert_eq!(StorageType::Memory, config.get_storage_type());
+                assert!(config.get_storage_path().is_none());
+                assert_eq!(
+                    MissedNotificationPolicy::Archive,
+                    config.get_missed_notification_policy()
+                );
+
                 let work_time = config.get_work_time();
                 assert_eq!(true, work_time.is_none());
 
                 let break_time = config.get_break_time();
                 assert_eq!(true, break_time.is_none());
+
+                assert!(config.get_long_break_time().is_none());
+                assert!(config.get_long_break_interval().is_none());
+                assert!(config.get_timezone().is_none());
+                assert!(config.get_webhooks().is_empty());
             });
     }
+
+    #[test]
+    fn test_telegram_configuration() {
+        let config: Configuration = serde_json::from_value(json!({
+            "telegram": {
+                "bot_token": "your-bot-token",
+                "chat_id": "@pomodoro",
+                "api_url": "http://localhost:8081"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Some(String::from("@pomodoro")),
+            config.get_telegram_chat_id()
+        );
+        assert_eq!("http://localhost:8081", config.get_telegram_api_url());
+    }
 }
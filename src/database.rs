@@ -1,274 +1,564 @@
 use chrono::SecondsFormat;
-use gluesql::core::ast_builder::{table, Build};
-use gluesql::prelude::{Glue, MemoryStorage, Payload};
-use std::sync::{Arc, Mutex};
-
+use gluesql::core::ast::Statement;
+use gluesql::core::ast_builder::{col, null, num, table, text, Build, ExprNode};
+use gluesql::core::data::Value;
+use gluesql::core::result::Result as GlueResult;
+use gluesql::prelude::{Glue, MemoryStorage, Payload, PayloadVariable, SledStorage};
+use std::sync::MutexGuard;
+
+use crate::configuration::{Configuration, StorageType};
+use crate::error::DatabaseError;
 use crate::ArcGlue;
 use crate::{archived_notification::ArchivedNotification, notification::Notification};
 
-pub fn get_memory_glue() -> Glue<MemoryStorage> {
-    let storage = MemoryStorage::default();
+/// StorageGlue wraps the glue of the storage chosen in the configuration
+/// so that the rest of the app does not need to know which one is used.
+pub enum StorageGlue {
+    Memory(Glue<MemoryStorage>),
+    Sled(Glue<SledStorage>),
+}
 
-    Glue::new(storage)
-}
-
-pub async fn initialize(glue: Arc<Mutex<Glue<MemoryStorage>>>) {
-    let mut glue = glue.lock().unwrap();
-
-    let sql_stmts = vec![
-        table("notifications")
-            .drop_table_if_exists()
-            .build()
-            .unwrap(),
-        table("notifications")
-            .create_table()
-            .add_column("id INTEGER")
-            .add_column("description TEXT")
-            .add_column("work_time INTEGER")
-            .add_column("break_time INTEGER")
-            .add_column("created_at TIMESTAMP")
-            .add_column("work_expired_at TIMESTAMP")
-            .add_column("break_expired_at TIMESTAMP")
-            .build()
-            .unwrap(),
-        table("archived_notifications")
-            .drop_table_if_exists()
-            .build()
-            .unwrap(),
-        table("archived_notifications")
-            .create_table()
-            .add_column("id INTEGER")
-            .add_column("description TEXT")
-            .add_column("work_time INTEGER")
-            .add_column("break_time INTEGER")
-            .add_column("created_at TIMESTAMP")
-            .add_column("work_expired_at TIMESTAMP")
-            .add_column("break_expired_at TIMESTAMP")
-            .build()
-            .unwrap(),
-    ];
+impl StorageGlue {
+    pub fn execute(&mut self, sql: &str) -> GlueResult<Vec<Payload>> {
+        match self {
+            StorageGlue::Memory(glue) => glue.execute(sql),
+            StorageGlue::Sled(glue) => glue.execute(sql),
+        }
+    }
 
-    for stmt in sql_stmts {
-        let output = glue.execute_stmt(&stmt).unwrap();
-        debug!("output: {:?}", output);
+    pub fn execute_stmt(&mut self, statement: &Statement) -> GlueResult<Payload> {
+        match self {
+            StorageGlue::Memory(glue) => glue.execute_stmt(statement),
+            StorageGlue::Sled(glue) => glue.execute_stmt(statement),
+        }
     }
 }
 
-//TODO(young): Handle error
-pub async fn create_notification(glue: ArcGlue, notification: &Notification) {
-    let mut glue = glue.lock().unwrap();
-
-    let (id, desc, work_time, break_time, created_at, w_expired_at, b_expired_at) =
-        notification.get_values();
+pub fn get_glue(configuration: &Configuration) -> Result<StorageGlue, DatabaseError> {
+    match configuration.get_storage_type() {
+        StorageType::Memory => Ok(get_memory_glue()),
+        StorageType::Sled => {
+            let path = configuration
+                .get_storage_path()
+                .as_ref()
+                .ok_or(DatabaseError::EmptyStoragePath)?;
 
-    let sql = format!(
-        r#"
-        INSERT INTO notifications VALUES ({}, '{}', {}, {}, '{}', '{}', '{}');
-    "#,
-        id,
-        desc,
-        work_time,
-        break_time,
-        created_at.to_rfc3339_opts(SecondsFormat::Millis, true),
-        w_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true),
-        b_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true)
-    );
+            get_sled_glue(path)
+        }
+    }
+}
 
-    debug!("create sql: {}", sql);
+pub fn get_memory_glue() -> StorageGlue {
+    let storage = MemoryStorage::default();
 
-    let output = glue.execute(sql.as_str()).unwrap();
-    debug!("output: {:?}", output);
+    StorageGlue::Memory(Glue::new(storage))
 }
 
-pub async fn read_last_expired_notification(glue: ArcGlue) -> Option<Notification> {
-    let mut glue = glue.lock().unwrap();
+pub fn get_sled_glue(path: &str) -> Result<StorageGlue, DatabaseError> {
+    let storage = SledStorage::new(path).map_err(DatabaseError::OpenFail)?;
+
+    Ok(StorageGlue::Sled(Glue::new(storage)))
+}
 
-    let sql = String::from(
-        r#"
-        SELECT * FROM notifications ORDER BY break_expired_at DESC, work_expired_at DESC LIMIT 1;
-        "#,
+const NOTIFICATION_TABLES: [&str; 2] = ["notifications", "archived_notifications"];
+
+type Migration = fn(&mut StorageGlue) -> Result<(), DatabaseError>;
+
+/// MIGRATIONS[n] migrates the tables of the schema version n to n + 1.
+/// Add a step here whenever the schema or the meaning of a column changes.
+const MIGRATIONS: [Migration; 3] = [
+    add_missing_columns,
+    convert_minutes_to_seconds,
+    // adds queued_after, which came after the conversion
+    add_missing_columns,
+];
+
+const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// The columns added after the tables were introduced, in the order of the schema.
+/// Rows stored before get the default values, e.g. not paused and not a part of a cycle.
+const ADDED_COLUMNS: [(&str, &str); 5] = [
+    ("paused_at", "TIMESTAMP NULL"),
+    ("cut_short", "BOOLEAN DEFAULT FALSE"),
+    ("cycle_index", "INTEGER DEFAULT 0"),
+    ("cycle_total", "INTEGER DEFAULT 0"),
+    ("queued_after", "INTEGER NULL"),
+];
+
+/// initialize creates the tables only if they are missing,
+/// so that notifications stored in a persistent storage survive restarts.
+/// Tables created by an older version of pomodoro are migrated to the current schema.
+pub async fn initialize(glue: ArcGlue) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    // tables created before the schema version was introduced have no version
+    let has_tables = list_tables(&mut glue)?
+        .iter()
+        .any(|name| name == "notifications");
+
+    let mut sql_stmts: Vec<_> = NOTIFICATION_TABLES
+        .iter()
+        .map(|table_name| {
+            table(table_name)
+                .create_table_if_not_exists()
+                .add_column("id INTEGER")
+                .add_column("description TEXT")
+                .add_column("work_time INTEGER")
+                .add_column("break_time INTEGER")
+                .add_column("created_at TIMESTAMP")
+                .add_column("work_expired_at TIMESTAMP")
+                .add_column("break_expired_at TIMESTAMP")
+                .add_column("paused_at TIMESTAMP NULL")
+                .add_column("cut_short BOOLEAN")
+                .add_column("cycle_index INTEGER")
+                .add_column("cycle_total INTEGER")
+                .add_column("queued_after INTEGER NULL")
+                .build()
+        })
+        .collect();
+    sql_stmts.push(
+        table("schema_version")
+            .create_table_if_not_exists()
+            .add_column("version_number INTEGER")
+            .build(),
     );
-    debug!("sql: {:?}", sql);
 
-    let output = glue.execute(sql.as_str()).unwrap().swap_remove(0);
-    debug!("output: {:?}", output);
+    for stmt in sql_stmts {
+        execute(&mut glue, stmt)?;
+    }
 
-    match output {
-        Payload::Select {
-            labels: _,
-            mut rows,
-        } => {
-            if rows.is_empty() {
-                None
-            } else {
-                Some(Notification::convert_to_notification(rows.swap_remove(0)))
-            }
-        }
-        _ => {
-            panic!("no such case!");
+    let version = match read_schema_version(&mut glue)? {
+        Some(version) => version,
+        None => {
+            let version = if has_tables { 0 } else { SCHEMA_VERSION };
+            let sql_stmt = table("schema_version")
+                .insert()
+                .values(vec![vec![num(version)]])
+                .build();
+            execute(&mut glue, sql_stmt)?;
+
+            version
         }
-    }
+    };
+
+    migrate(&mut glue, version)
 }
 
-pub async fn read_notification(glue: ArcGlue, id: u16) -> Option<Notification> {
-    let mut glue = glue.lock().unwrap();
+/// migrate brings the tables of the given schema version up to date, one version at a time.
+fn migrate(glue: &mut StorageGlue, version: i64) -> Result<(), DatabaseError> {
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version.max(0) as usize) {
+        info!("migrating the database from schema version {}", from);
+        migration(glue)?;
+
+        let sql_stmt = table("schema_version")
+            .update()
+            .set("version_number", num(from as i64 + 1))
+            .build();
+        execute(glue, sql_stmt)?;
+    }
 
-    let sql_stmt = table("notifications")
-        .select()
-        .filter(format!("id = {}", id).as_str())
-        .build()
-        .unwrap();
-    debug!("sql_stmt: {:?}", sql_stmt);
+    Ok(())
+}
 
-    let output = glue.execute_stmt(&sql_stmt).unwrap();
-    debug!("output: {:?}", output);
+/// add_missing_columns adds the columns which the tables created by an older version lack.
+fn add_missing_columns(glue: &mut StorageGlue) -> Result<(), DatabaseError> {
+    for table_name in NOTIFICATION_TABLES {
+        let columns = list_columns(glue, table_name)?;
 
-    match output {
-        Payload::Select {
-            labels: _,
-            mut rows,
-        } => {
-            if rows.is_empty() {
-                None
-            } else {
-                Some(Notification::convert_to_notification(rows.swap_remove(0)))
+        for (column, definition) in ADDED_COLUMNS {
+            if columns.iter().any(|c| c == column) {
+                continue;
             }
-        }
-        _ => {
-            panic!("no such case!");
+
+            // the statement is made of the constants only, so nothing is interpreted as sql
+            execute_sql(
+                glue,
+                &format!(
+                    "ALTER TABLE {} ADD COLUMN {} {};",
+                    table_name, column, definition
+                ),
+            )?;
         }
     }
+
+    Ok(())
 }
 
-pub async fn list_notification(glue: ArcGlue) -> Vec<Notification> {
-    let mut glue = glue.lock().unwrap();
+/// convert_minutes_to_seconds converts work_time and break_time stored in minutes by an older version.
+/// The timestamps are kept as they are since they do not depend on the unit.
+fn convert_minutes_to_seconds(glue: &mut StorageGlue) -> Result<(), DatabaseError> {
+    for table_name in NOTIFICATION_TABLES {
+        execute_sql(
+            glue,
+            &format!(
+                "UPDATE {} SET work_time = work_time * 60, break_time = break_time * 60;",
+                table_name
+            ),
+        )?;
+    }
 
-    let sql_stmt = table("notifications").select().build().unwrap();
+    Ok(())
+}
 
-    let output = glue.execute_stmt(&sql_stmt).unwrap();
-    debug!("output: {:?}", output);
+fn read_schema_version(glue: &mut StorageGlue) -> Result<Option<i64>, DatabaseError> {
+    let output = execute(glue, table("schema_version").select().build())?;
 
     match output {
-        Payload::Select { labels: _, rows } => rows
-            .into_iter()
-            .map(Notification::convert_to_notification)
-            .collect(),
-        _ => {
-            panic!("no such case!");
-        }
+        Payload::Select { labels: _, rows } => match rows.first() {
+            Some(row) => match row.get_value_by_index(0) {
+                Some(Value::I64(version)) => Ok(Some(*version)),
+                _ => Err(DatabaseError::InvalidRow(String::from(
+                    "schema version type mismatch",
+                ))),
+            },
+            None => Ok(None),
+        },
+        _ => Err(DatabaseError::UnexpectedPayload),
     }
 }
 
-pub async fn list_archived_notification(glue: ArcGlue) -> Vec<ArchivedNotification> {
-    let mut glue = glue.lock().unwrap();
+fn list_tables(glue: &mut StorageGlue) -> Result<Vec<String>, DatabaseError> {
+    match execute_sql(glue, "SHOW TABLES;")? {
+        Payload::ShowVariable(PayloadVariable::Tables(names)) => Ok(names),
+        _ => Err(DatabaseError::UnexpectedPayload),
+    }
+}
 
-    let sql = "SELECT * FROM archived_notifications ORDER BY id DESC;";
+fn list_columns(glue: &mut StorageGlue, table_name: &str) -> Result<Vec<String>, DatabaseError> {
+    match execute_sql(glue, &format!("SHOW COLUMNS FROM {};", table_name))? {
+        Payload::ShowColumns(columns) => Ok(columns.into_iter().map(|(name, _)| name).collect()),
+        _ => Err(DatabaseError::UnexpectedPayload),
+    }
+}
+
+fn lock(glue: &ArcGlue) -> Result<MutexGuard<'_, StorageGlue>, DatabaseError> {
+    glue.lock().map_err(|_| DatabaseError::LockFail)
+}
+
+fn execute(
+    glue: &mut StorageGlue,
+    sql_stmt: GlueResult<Statement>,
+) -> Result<Payload, DatabaseError> {
+    let sql_stmt = sql_stmt.map_err(DatabaseError::BuildFail)?;
+    debug!("sql_stmt: {:?}", sql_stmt);
 
-    let output = glue.execute(sql).unwrap().swap_remove(0);
+    let output = glue
+        .execute_stmt(&sql_stmt)
+        .map_err(DatabaseError::ExecuteFail)?;
     debug!("output: {:?}", output);
 
+    Ok(output)
+}
+
+/// execute_sql runs the sql which the ast builder can not build, e.g. the statements of migrations
+fn execute_sql(glue: &mut StorageGlue, sql: &str) -> Result<Payload, DatabaseError> {
+    debug!("sql: {}", sql);
+
+    let output = glue
+        .execute(sql)
+        .map_err(DatabaseError::ExecuteFail)?
+        .pop()
+        .ok_or(DatabaseError::UnexpectedPayload)?;
+    debug!("output: {:?}", output);
+
+    Ok(output)
+}
+
+fn boolean(value: bool) -> ExprNode<'static> {
+    ExprNode::from(if value { "TRUE" } else { "FALSE" })
+}
+
+fn convert_to_notifications(output: Payload) -> Result<Vec<Notification>, DatabaseError> {
     match output {
         Payload::Select { labels: _, rows } => rows
             .into_iter()
-            // TODO(young): As of now archived_notifications schema is same as notifications table
             .map(Notification::convert_to_notification)
-            .map(ArchivedNotification::from)
             .collect(),
-        _ => {
-            panic!("no such case!");
-        }
+        _ => Err(DatabaseError::UnexpectedPayload),
     }
 }
 
-pub async fn delete_and_archive_notification(glue: ArcGlue, id: u16) {
-    archive_notification(glue.clone(), id).await;
-    delete_notification(glue.clone(), id).await;
+/// insert_notifications inserts notifications to the table using ast builder,
+/// so that values like description are never interpreted as sql.
+fn insert_notifications(
+    glue: &mut StorageGlue,
+    table_name: &str,
+    notifications: &[Notification],
+) -> Result<(), DatabaseError> {
+    if notifications.is_empty() {
+        return Ok(());
+    }
+
+    let values = notifications
+        .iter()
+        .map(|notification| {
+            let (id, desc, work_time, break_time, created_at, w_expired_at, b_expired_at) =
+                notification.get_values();
+            let paused_at = match notification.get_paused_at() {
+                Some(paused_at) => text(paused_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                None => null(),
+            };
+            let (cycle_index, cycle_total) = notification.get_cycle().unwrap_or((0, 0));
+            let queued_after = match notification.get_queued_after() {
+                Some(queued_after) => num(i64::from(queued_after)),
+                None => null(),
+            };
+
+            vec![
+                num(i64::from(id)),
+                text(desc),
+                num(i64::from(work_time)),
+                num(i64::from(break_time)),
+                text(created_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                text(w_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                text(b_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                paused_at,
+                boolean(notification.is_cut_short()),
+                num(i64::from(cycle_index)),
+                num(i64::from(cycle_total)),
+                queued_after,
+            ]
+        })
+        .collect();
+
+    let sql_stmt = table(table_name).insert().values(values).build();
+    execute(glue, sql_stmt)?;
+
+    Ok(())
+}
+
+pub async fn create_notification(
+    glue: ArcGlue,
+    notification: &Notification,
+) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    insert_notifications(
+        &mut glue,
+        "notifications",
+        std::slice::from_ref(notification),
+    )
+}
+
+/// create_archived_notifications inserts the notifications, e.g. imported ones, to the history.
+pub async fn create_archived_notifications(
+    glue: ArcGlue,
+    notifications: &[Notification],
+) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    insert_notifications(&mut glue, "archived_notifications", notifications)
+}
+
+/// update_notification overwrites the timestamps and the times of the stored notification
+/// with the ones of the given notification.
+pub async fn update_notification(
+    glue: ArcGlue,
+    notification: &Notification,
+) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let (id, _, work_time, break_time, created_at, w_expired_at, b_expired_at) =
+        notification.get_values();
+    let paused_at = match notification.get_paused_at() {
+        Some(paused_at) => text(paused_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        None => null(),
+    };
+
+    let sql_stmt = table("notifications")
+        .update()
+        .set("work_time", num(i64::from(work_time)))
+        .set("break_time", num(i64::from(break_time)))
+        .set(
+            "created_at",
+            text(created_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        )
+        .set(
+            "work_expired_at",
+            text(w_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        )
+        .set(
+            "break_expired_at",
+            text(b_expired_at.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        )
+        .set("paused_at", paused_at)
+        .set("cut_short", boolean(notification.is_cut_short()))
+        .filter(col("id").eq(num(i64::from(id))))
+        .build();
+
+    execute(&mut glue, sql_stmt)?;
+
+    Ok(())
 }
 
-//TODO(young): Handle error?
-pub async fn delete_notification(glue: ArcGlue, id: u16) {
-    let mut glue = glue.lock().unwrap();
+pub async fn read_last_expired_notification(
+    glue: ArcGlue,
+) -> Result<Option<Notification>, DatabaseError> {
+    let mut glue = lock(&glue)?;
 
-    // check if notification exists. It's okay. glue executes commands sequentially as of now.
     let sql_stmt = table("notifications")
         .select()
-        .filter(format!("id = {}", id).as_str())
-        .build()
-        .unwrap();
-    debug!("sql_stmt: {:?}", sql_stmt);
+        .order_by("break_expired_at DESC, work_expired_at DESC")
+        .limit(1)
+        .build();
 
-    let output = glue.execute_stmt(&sql_stmt).unwrap();
-    debug!("output: {:?}", output);
+    let output = execute(&mut glue, sql_stmt)?;
+
+    Ok(convert_to_notifications(output)?.into_iter().next())
+}
 
+pub async fn read_notification(
+    glue: ArcGlue,
+    id: u16,
+) -> Result<Option<Notification>, DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    select_notification(&mut glue, id)
+}
+
+fn select_notification(
+    glue: &mut StorageGlue,
+    id: u16,
+) -> Result<Option<Notification>, DatabaseError> {
     let sql_stmt = table("notifications")
-        .delete()
-        .filter(format!("id = {}", id).as_str())
-        .build()
-        .unwrap();
-    debug!("delete sql_stmt: {:?}", sql_stmt);
+        .select()
+        .filter(col("id").eq(num(i64::from(id))))
+        .build();
 
-    let output = glue.execute_stmt(&sql_stmt).unwrap();
-    debug!("output: {:?}", output);
+    let output = execute(glue, sql_stmt)?;
+
+    Ok(convert_to_notifications(output)?.into_iter().next())
 }
 
-//TODO(young): Handle error?
-pub async fn archive_notification(glue: ArcGlue, id: u16) {
-    let mut glue = glue.lock().unwrap();
+pub async fn list_notification(glue: ArcGlue) -> Result<Vec<Notification>, DatabaseError> {
+    let mut glue = lock(&glue)?;
 
-    let sql = format!(
-        r#"
-    INSERT INTO archived_notifications
-    SELECT * FROM notifications WHERE id = {};
-    "#,
-        id
-    );
+    let sql_stmt = table("notifications").select().build();
 
-    debug!("sql: {:?}", sql);
+    let output = execute(&mut glue, sql_stmt)?;
 
-    let output = glue.execute(sql.as_str()).unwrap();
-    debug!("output: {:?}", output);
+    convert_to_notifications(output)
 }
 
-pub async fn archive_all_notification(glue: ArcGlue) {
-    let mut glue = glue.lock().unwrap();
+pub async fn read_archived_notification(
+    glue: ArcGlue,
+    id: u16,
+) -> Result<Option<ArchivedNotification>, DatabaseError> {
+    let mut glue = lock(&glue)?;
 
-    let sql = r#"
-    INSERT INTO archived_notifications
-    SELECT * FROM notifications;
-    "#;
+    let sql_stmt = table("archived_notifications")
+        .select()
+        .filter(col("id").eq(num(i64::from(id))))
+        .build();
 
-    debug!("archive all sql: {}", sql);
+    let output = execute(&mut glue, sql_stmt)?;
 
-    let output = glue.execute(sql).unwrap();
-    debug!("output: {:?}", output);
+    Ok(convert_to_notifications(output)?
+        .into_iter()
+        .map(ArchivedNotification::from)
+        .next())
 }
 
-pub async fn delete_all_archived_notification(glue: ArcGlue) {
-    let mut glue = glue.lock().unwrap();
+pub async fn list_archived_notification(
+    glue: ArcGlue,
+) -> Result<Vec<ArchivedNotification>, DatabaseError> {
+    let mut glue = lock(&glue)?;
 
-    let sql = r#"
-    DELETE FROM archived_notifications;
-    "#;
+    let sql_stmt = table("archived_notifications")
+        .select()
+        .order_by("id DESC")
+        .build();
 
-    debug!("delete all sql: {}", sql);
+    let output = execute(&mut glue, sql_stmt)?;
 
-    let output = glue.execute(sql).unwrap();
-    debug!("output: {:?}", output);
+    // TODO(young): As of now archived_notifications schema is same as notifications table
+    let archived_notifications = convert_to_notifications(output)?
+        .into_iter()
+        .map(ArchivedNotification::from)
+        .collect();
+
+    Ok(archived_notifications)
 }
 
-pub async fn delete_all_notification(glue: ArcGlue) {
-    let mut glue = glue.lock().unwrap();
+/// read_last_id returns the biggest id among notifications and archived notifications.
+/// It returns 0 if there is no notification at all.
+pub async fn read_last_id(glue: ArcGlue) -> Result<u16, DatabaseError> {
+    let last_notification_id = list_notification(glue.clone())
+        .await?
+        .iter()
+        .map(|n| n.get_id())
+        .max()
+        .unwrap_or(0);
+
+    let last_archived_notification_id = list_archived_notification(glue)
+        .await?
+        .iter()
+        .map(|n| n.get_id())
+        .max()
+        .unwrap_or(0);
+
+    Ok(last_notification_id.max(last_archived_notification_id))
+}
 
-    let sql_stmt = table("notifications").delete().build().unwrap();
-    debug!("delete sql_stmt: {:?}", sql_stmt);
+pub async fn delete_and_archive_notification(glue: ArcGlue, id: u16) -> Result<(), DatabaseError> {
+    archive_notification(glue.clone(), id).await?;
+    delete_notification(glue.clone(), id).await
+}
 
-    let output = glue.execute_stmt(&sql_stmt).unwrap();
-    debug!("output: {:?}", output);
+pub async fn delete_notification(glue: ArcGlue, id: u16) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let sql_stmt = table("notifications")
+        .delete()
+        .filter(col("id").eq(num(i64::from(id))))
+        .build();
+
+    execute(&mut glue, sql_stmt)?;
+
+    Ok(())
 }
 
-pub async fn delete_and_archive_all_notification(glue: ArcGlue) {
-    archive_all_notification(glue.clone()).await;
-    delete_all_notification(glue.clone()).await;
+pub async fn archive_notification(glue: ArcGlue, id: u16) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let notifications: Vec<Notification> =
+        select_notification(&mut glue, id)?.into_iter().collect();
+
+    insert_notifications(&mut glue, "archived_notifications", &notifications)
+}
+
+pub async fn archive_all_notification(glue: ArcGlue) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let sql_stmt = table("notifications").select().build();
+    let notifications = convert_to_notifications(execute(&mut glue, sql_stmt)?)?;
+
+    insert_notifications(&mut glue, "archived_notifications", &notifications)
+}
+
+pub async fn delete_all_archived_notification(glue: ArcGlue) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let sql_stmt = table("archived_notifications").delete().build();
+
+    execute(&mut glue, sql_stmt)?;
+
+    Ok(())
+}
+
+pub async fn delete_all_notification(glue: ArcGlue) -> Result<(), DatabaseError> {
+    let mut glue = lock(&glue)?;
+
+    let sql_stmt = table("notifications").delete().build();
+
+    execute(&mut glue, sql_stmt)?;
+
+    Ok(())
+}
+
+pub async fn delete_and_archive_all_notification(glue: ArcGlue) -> Result<(), DatabaseError> {
+    archive_all_notification(glue.clone()).await?;
+    delete_all_notification(glue.clone()).await
 }
 
 #[cfg(test)]
@@ -281,7 +571,8 @@ mod tests {
         archive_all_notification, archive_notification, create_notification,
         delete_all_archived_notification, delete_all_notification, delete_notification,
         get_memory_glue, initialize, list_archived_notification, list_notification,
-        read_last_expired_notification, read_notification,
+        read_archived_notification, read_last_expired_notification, read_last_id,
+        read_notification, update_notification,
     };
     use std::{
         panic,
@@ -291,7 +582,7 @@ mod tests {
     #[tokio::test]
     async fn test_initialize_tables() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let sql = "SHOW TABLES;";
         let output = glue.lock().unwrap().execute(sql).unwrap().swap_remove(0);
@@ -300,9 +591,10 @@ mod tests {
             Payload::ShowVariable(PayloadVariable::Tables(mut names)) => {
                 names.sort();
 
-                assert_eq!(2, names.len());
+                assert_eq!(3, names.len());
                 assert_eq!("archived_notifications", names[0]);
                 assert_eq!("notifications", names[1]);
+                assert_eq!("schema_version", names[2]);
             }
             _ => {
                 panic!("no such case");
@@ -310,17 +602,84 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_initialize_keeps_existing_rows() {
+        let glue = Arc::new(Mutex::new(get_memory_glue()));
+        initialize(glue.clone()).await.unwrap();
+
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, Utc::now());
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        initialize(glue.clone()).await.unwrap();
+
+        let result = list_notification(glue.clone()).await.unwrap();
+        assert_eq!(1, result.len());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_migrates_old_schema() {
+        let glue = Arc::new(Mutex::new(get_memory_glue()));
+        {
+            let mut glue = glue.lock().unwrap();
+            for table_name in ["notifications", "archived_notifications"] {
+                let sql = format!(
+                    "CREATE TABLE {} (id INTEGER, description TEXT, work_time INTEGER, break_time INTEGER, \
+                    created_at TIMESTAMP, work_expired_at TIMESTAMP, break_expired_at TIMESTAMP);",
+                    table_name
+                );
+                glue.execute(&sql).unwrap();
+            }
+            glue.execute(
+                "INSERT INTO notifications VALUES (1, 'sample', 25, 5, \
+                '2023-03-01T09:00:00.000Z', '2023-03-01T09:25:00.000Z', '2023-03-01T09:30:00.000Z');",
+            )
+            .unwrap();
+        }
+
+        initialize(glue.clone()).await.unwrap();
+
+        let result = read_notification(glue.clone(), 1).await.unwrap().unwrap();
+        assert_eq!("sample", result.get_description());
+        let (_, _, work_time, break_time, _, _, _) = result.get_values();
+        assert_eq!((25 * 60, 5 * 60), (work_time, break_time));
+        assert!(!result.is_paused());
+        assert!(!result.is_cut_short());
+        assert!(result.get_cycle().is_none());
+        assert!(result.get_queued_after().is_none());
+
+        let notification = Notification::new(2, "sample", 25 * 60, 5 * 60, Utc::now())
+            .with_cycle(1, 4)
+            .with_queued_after(Some(1));
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+        archive_notification(glue.clone(), 2).await.unwrap();
+
+        // the migration runs only once
+        initialize(glue.clone()).await.unwrap();
+
+        let result = list_notification(glue.clone()).await.unwrap();
+        assert_eq!(2, result.len());
+        assert_eq!(25 * 60, result[0].get_values().2);
+        let result = read_archived_notification(glue.clone(), 2).await.unwrap();
+        assert!(result.is_some());
+    }
+
     #[tokio::test]
     async fn test_create_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
 
-        create_notification(glue.clone(), &notification).await;
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let result = read_notification(glue.clone(), 0).await.unwrap();
+        let result = read_notification(glue.clone(), 0).await.unwrap().unwrap();
         assert_eq!(
             0,
             result.get_id(),
@@ -334,25 +693,99 @@ mod tests {
                 .get_start_at()
                 .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
         );
+        assert!(result.get_queued_after().is_none());
+
+        let notification =
+            Notification::new(1, "sample", 25 * 60, 5 * 60, now).with_queued_after(Some(0));
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        let result = read_notification(glue.clone(), 1).await.unwrap().unwrap();
+        assert_eq!(Some(0), result.get_queued_after());
+    }
+
+    #[tokio::test]
+    async fn test_create_notification_with_quoted_description() {
+        let glue = Arc::new(Mutex::new(get_memory_glue()));
+        initialize(glue.clone()).await.unwrap();
+
+        let description = "it's \"quoted\"'); DELETE FROM notifications; --";
+        let notification = Notification::new(0, description, 25 * 60, 5 * 60, Utc::now());
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        let result = read_notification(glue.clone(), 0).await.unwrap().unwrap();
+        assert_eq!(description, result.get_description());
+
+        archive_notification(glue.clone(), 0).await.unwrap();
+        let result = list_archived_notification(glue.clone()).await.unwrap();
+        assert_eq!(1, result.len());
+
+        let result = read_archived_notification(glue.clone(), 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(description, result.get_description());
+    }
+
+    #[tokio::test]
+    async fn test_update_notification() {
+        let glue = Arc::new(Mutex::new(get_memory_glue()));
+        initialize(glue.clone()).await.unwrap();
+
+        let now = Utc::now();
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        notification.pause(now);
+        update_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        let result = read_notification(glue.clone(), 0).await.unwrap().unwrap();
+        assert!(result.is_paused());
+
+        notification.resume(now + chrono::Duration::minutes(10));
+        update_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        let result = read_notification(glue.clone(), 0).await.unwrap().unwrap();
+        assert!(!result.is_paused());
+        assert_eq!(
+            (now + chrono::Duration::minutes(40))
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            result
+                .get_last_expired_at()
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        );
     }
 
     #[tokio::test]
     async fn test_list_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         // empty row
-        let result = list_notification(glue.clone()).await;
+        let result = list_notification(glue.clone()).await.unwrap();
         assert_eq!(0, result.len());
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let notification = Notification::new(1, 30, 10, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(1, "sample", 30 * 60, 10 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let result = list_notification(glue.clone()).await;
+        let result = list_notification(glue.clone()).await.unwrap();
         assert_eq!(2, result.len());
         assert_eq!(0, result[0].get_id());
         assert_eq!(1, result[1].get_id());
@@ -361,107 +794,150 @@ mod tests {
     #[tokio::test]
     async fn test_delete_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        delete_notification(glue.clone(), 0).await;
-        let result = read_notification(glue.clone(), 0).await;
+        delete_notification(glue.clone(), 0).await.unwrap();
+        let result = read_notification(glue.clone(), 0).await.unwrap();
         assert!(result.is_none());
     }
 
     #[tokio::test]
     async fn test_delete_all_notifications() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
-
-        let notification = Notification::new(1, 30, 10, now);
-        create_notification(glue.clone(), &notification).await;
-
-        delete_all_notification(glue.clone()).await;
-        let result = list_notification(glue.clone()).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        let notification = Notification::new(1, "sample", 30 * 60, 10 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        delete_all_notification(glue.clone()).await.unwrap();
+        let result = list_notification(glue.clone()).await.unwrap();
         assert!(result.is_empty());
     }
 
     #[tokio::test]
     async fn test_archive_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let notification = Notification::new(1, 30, 10, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(1, "sample", 30 * 60, 10 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        archive_all_notification(glue.clone()).await;
+        archive_all_notification(glue.clone()).await.unwrap();
 
-        let result = list_notification(glue.clone()).await;
+        let result = list_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 2);
 
-        let result = list_archived_notification(glue.clone()).await;
+        let result = list_archived_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 2);
     }
 
     #[tokio::test]
     async fn test_archive_all_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        archive_notification(glue.clone(), 0).await;
+        archive_notification(glue.clone(), 0).await.unwrap();
 
-        let result = list_notification(glue.clone()).await;
+        let result = list_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 1);
 
-        let result = list_archived_notification(glue.clone()).await;
+        let result = list_archived_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 1);
     }
 
     #[tokio::test]
     async fn test_delete_all_archived_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        archive_notification(glue.clone(), 0).await;
+        archive_notification(glue.clone(), 0).await.unwrap();
 
-        let result = list_archived_notification(glue.clone()).await;
+        let result = list_archived_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 1);
 
-        delete_all_archived_notification(glue.clone()).await;
+        delete_all_archived_notification(glue.clone())
+            .await
+            .unwrap();
 
-        let result = list_archived_notification(glue.clone()).await;
+        let result = list_archived_notification(glue.clone()).await.unwrap();
         assert!(result.len() == 0);
     }
 
     #[tokio::test]
     async fn test_read_last_expired_notification() {
         let glue = Arc::new(Mutex::new(get_memory_glue()));
-        initialize(glue.clone()).await;
+        initialize(glue.clone()).await.unwrap();
 
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let notification = Notification::new(1, 30, 10, now);
-        create_notification(glue.clone(), &notification).await;
+        let notification = Notification::new(1, "sample", 30 * 60, 10 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
 
-        let result = read_last_expired_notification(glue.clone()).await;
+        let result = read_last_expired_notification(glue.clone()).await.unwrap();
         assert!(result.is_some());
         assert_eq!(1, result.unwrap().get_id());
     }
+
+    #[tokio::test]
+    async fn test_read_last_id() {
+        let glue = Arc::new(Mutex::new(get_memory_glue()));
+        initialize(glue.clone()).await.unwrap();
+
+        assert_eq!(0, read_last_id(glue.clone()).await.unwrap());
+
+        let now = Utc::now();
+        let notification = Notification::new(3, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+        archive_notification(glue.clone(), 3).await.unwrap();
+        delete_notification(glue.clone(), 3).await.unwrap();
+
+        let notification = Notification::new(2, "sample", 25 * 60, 5 * 60, now);
+        create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        assert_eq!(3, read_last_id(glue.clone()).await.unwrap());
+    }
 }
@@ -1,8 +1,10 @@
 use bincode::error::{DecodeError, EncodeError};
+use gluesql::core::result::Error as GlueError;
 use notify_rust::error::Error as NotifyRustError;
 use reqwest::Error as RequestError;
 use serde_json::error::Error as SerdeJsonError;
 use std::{error::Error, fmt, io, result};
+use tokio::task::JoinError;
 
 pub type NotifyResult = result::Result<(), NotificationError>;
 
@@ -22,10 +24,26 @@ pub enum NotificationError {
     Desktop(NotifyRustError),
     Slack(RequestError),
     Discord(RequestError),
+    Telegram(RequestError),
+    Matrix(RequestError),
+    InvalidMatrix(String),
+    Ntfy(RequestError),
+    Gotify(RequestError),
+    Webhook(RequestError),
+    InvalidWebhook(String),
+    // the task of the notifier panicked
+    Task(JoinError),
     EmptyConfiguration,
     NewNotification(ParseError),
     DeletionFail(String),
+    PauseFail(String),
+    ResumeFail(String),
+    ExtendFail(String),
+    SnoozeFail(String),
+    SkipFail(String),
     EmptyTimeValues,
+    IdExhausted,
+    Database(DatabaseError),
 }
 
 impl fmt::Display for NotificationError {
@@ -34,15 +52,30 @@ impl fmt::Display for NotificationError {
             NotificationError::Desktop(_) => write!(f, "NotificationError::Desktop"),
             NotificationError::Slack(_) => write!(f, "NotificationError::Slack"),
             NotificationError::Discord(_) => write!(f, "NotificationError::Discord"),
+            NotificationError::Telegram(_) => write!(f, "NotificationError::Telegram"),
+            NotificationError::Matrix(_) => write!(f, "NotificationError::Matrix"),
+            NotificationError::InvalidMatrix(msg) => write!(f, "{}", msg),
+            NotificationError::Ntfy(_) => write!(f, "NotificationError::Ntfy"),
+            NotificationError::Gotify(_) => write!(f, "NotificationError::Gotify"),
+            NotificationError::Webhook(_) => write!(f, "NotificationError::Webhook"),
+            NotificationError::InvalidWebhook(msg) => write!(f, "{}", msg),
+            NotificationError::Task(_) => write!(f, "NotificationError::Task"),
             NotificationError::EmptyConfiguration => write!(f, "configuration is empty"),
             NotificationError::NewNotification(e) => {
                 write!(f, "failed to get new notification: {}", e)
             }
             NotificationError::DeletionFail(msg) => write!(f, "{}", msg),
+            NotificationError::PauseFail(msg) => write!(f, "{}", msg),
+            NotificationError::ResumeFail(msg) => write!(f, "{}", msg),
+            NotificationError::ExtendFail(msg) => write!(f, "{}", msg),
+            NotificationError::SnoozeFail(msg) => write!(f, "{}", msg),
+            NotificationError::SkipFail(msg) => write!(f, "{}", msg),
             NotificationError::EmptyTimeValues => write!(
                 f,
                 "Cannot create a notification with 0 work time and 0 break time"
             ),
+            NotificationError::IdExhausted => write!(f, "no notification id is left to use"),
+            NotificationError::Database(e) => write!(f, "{}", e),
         }
     }
 }
@@ -53,10 +86,25 @@ impl std::error::Error for NotificationError {
             NotificationError::Desktop(ref e) => Some(e),
             NotificationError::Slack(ref e) => Some(e),
             NotificationError::Discord(ref e) => Some(e),
+            NotificationError::Telegram(ref e) => Some(e),
+            NotificationError::Matrix(ref e) => Some(e),
+            NotificationError::InvalidMatrix(_) => None,
+            NotificationError::Ntfy(ref e) => Some(e),
+            NotificationError::Gotify(ref e) => Some(e),
+            NotificationError::Webhook(ref e) => Some(e),
+            NotificationError::InvalidWebhook(_) => None,
+            NotificationError::Task(ref e) => Some(e),
             NotificationError::EmptyConfiguration => None,
             NotificationError::NewNotification(ref e) => Some(e),
             NotificationError::DeletionFail(_) => None,
+            NotificationError::PauseFail(_) => None,
+            NotificationError::ResumeFail(_) => None,
+            NotificationError::ExtendFail(_) => None,
+            NotificationError::SnoozeFail(_) => None,
+            NotificationError::SkipFail(_) => None,
             NotificationError::EmptyTimeValues => None,
+            NotificationError::IdExhausted => None,
+            NotificationError::Database(ref e) => Some(e),
         }
     }
 }
@@ -68,8 +116,17 @@ pub enum ConfigurationError {
     JsonError(SerdeJsonError),
     SlackConfigNotFound,
     DiscordConfigNotFound,
+    TelegramConfigNotFound,
+    MatrixConfigNotFound,
+    NtfyConfigNotFound,
+    GotifyConfigNotFound,
     UnspecifiedWorkTime,
     UnspecifiedBreakTime,
+    UnspecifiedLongBreakTime,
+    UnspecifiedLongBreakInterval,
+    UnspecifiedTimezone,
+    InvalidTimezone(String),
+    StoragePathNotFound,
     LoadFail(io::Error),
     // config json wrong format?
 }
@@ -86,8 +143,29 @@ impl fmt::Display for ConfigurationError {
             ConfigurationError::DiscordConfigNotFound => {
                 write!(f, "can not find discord config in json")
             }
+            ConfigurationError::TelegramConfigNotFound => {
+                write!(f, "can not find telegram config in json")
+            }
+            ConfigurationError::MatrixConfigNotFound => {
+                write!(f, "can not find matrix config in json")
+            }
+            ConfigurationError::NtfyConfigNotFound => {
+                write!(f, "can not find ntfy config in json")
+            }
+            ConfigurationError::GotifyConfigNotFound => {
+                write!(f, "can not find gotify config in json")
+            }
             ConfigurationError::UnspecifiedWorkTime => write!(f, "not specified"),
             ConfigurationError::UnspecifiedBreakTime => write!(f, "not specified"),
+            ConfigurationError::UnspecifiedLongBreakTime => write!(f, "not specified"),
+            ConfigurationError::UnspecifiedLongBreakInterval => write!(f, "not specified"),
+            ConfigurationError::UnspecifiedTimezone => write!(f, "not specified, local is used"),
+            ConfigurationError::InvalidTimezone(name) => {
+                write!(f, "invalid timezone ({}), local is used", name)
+            }
+            ConfigurationError::StoragePathNotFound => {
+                write!(f, "can not find storage path in json")
+            }
             ConfigurationError::LoadFail(e) => write!(f, "failed to load: {}", e),
         }
     }
@@ -101,13 +179,59 @@ impl std::error::Error for ConfigurationError {
             ConfigurationError::JsonError(ref e) => Some(e),
             ConfigurationError::SlackConfigNotFound => None,
             ConfigurationError::DiscordConfigNotFound => None,
+            ConfigurationError::TelegramConfigNotFound => None,
+            ConfigurationError::MatrixConfigNotFound => None,
+            ConfigurationError::NtfyConfigNotFound => None,
+            ConfigurationError::GotifyConfigNotFound => None,
             ConfigurationError::UnspecifiedWorkTime => None,
             ConfigurationError::UnspecifiedBreakTime => None,
+            ConfigurationError::UnspecifiedLongBreakTime => None,
+            ConfigurationError::UnspecifiedLongBreakInterval => None,
+            ConfigurationError::UnspecifiedTimezone => None,
+            ConfigurationError::InvalidTimezone(_) => None,
+            ConfigurationError::StoragePathNotFound => None,
             ConfigurationError::LoadFail(ref e) => Some(e),
         }
     }
 }
 
+#[derive(Debug)]
+pub enum HistoryError {
+    UnknownFormat(String),
+    Unsupported(String),
+    InvalidRecord(String),
+    Io(io::Error),
+    Json(SerdeJsonError),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::UnknownFormat(format) => write!(
+                f,
+                "unknown history format ({}), use csv, json or ics",
+                format
+            ),
+            HistoryError::Unsupported(msg) => write!(f, "{}", msg),
+            HistoryError::InvalidRecord(msg) => write!(f, "invalid history: {}", msg),
+            HistoryError::Io(e) => write!(f, "failed to access the history file: {}", e),
+            HistoryError::Json(e) => write!(f, "failed to handle json: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HistoryError::UnknownFormat(_) => None,
+            HistoryError::Unsupported(_) => None,
+            HistoryError::InvalidRecord(_) => None,
+            HistoryError::Io(ref e) => Some(e),
+            HistoryError::Json(ref e) => Some(e),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum UdsHandlerError {
     NoSubcommand,
@@ -141,6 +265,47 @@ impl std::error::Error for UdsHandlerError {
     }
 }
 
+#[derive(Debug)]
+pub enum DatabaseError {
+    OpenFail(GlueError),
+    EmptyStoragePath,
+    LockFail,
+    BuildFail(GlueError),
+    ExecuteFail(GlueError),
+    UnexpectedPayload,
+    InvalidRow(String),
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::OpenFail(_) => write!(f, "failed to open the storage"),
+            DatabaseError::EmptyStoragePath => {
+                write!(f, "storage path is required to use persistent storage")
+            }
+            DatabaseError::LockFail => write!(f, "failed to lock the database"),
+            DatabaseError::BuildFail(e) => write!(f, "failed to build sql statement: {}", e),
+            DatabaseError::ExecuteFail(e) => write!(f, "failed to execute sql statement: {}", e),
+            DatabaseError::UnexpectedPayload => write!(f, "database returned unexpected payload"),
+            DatabaseError::InvalidRow(msg) => write!(f, "invalid row: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DatabaseError::OpenFail(ref e) => Some(e),
+            DatabaseError::EmptyStoragePath => None,
+            DatabaseError::LockFail => None,
+            DatabaseError::BuildFail(ref e) => Some(e),
+            DatabaseError::ExecuteFail(ref e) => Some(e),
+            DatabaseError::UnexpectedPayload => None,
+            DatabaseError::InvalidRow(_) => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ParseError {
     message: Option<String>,
@@ -175,6 +340,8 @@ pub enum UserInputHandlerError {
     ParseError(ParseError),
     CommandMatchError(clap::Error),
     NotificationError(NotificationError),
+    DatabaseError(DatabaseError),
+    HistoryError(HistoryError),
 }
 
 impl fmt::Display for UserInputHandlerError {
@@ -188,6 +355,8 @@ impl fmt::Display for UserInputHandlerError {
                 write!(f, "failed to get matches: {}", e.render().ansi())
             }
             UserInputHandlerError::NotificationError(e) => write!(f, "{}", e),
+            UserInputHandlerError::DatabaseError(e) => write!(f, "{}", e),
+            UserInputHandlerError::HistoryError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -199,6 +368,8 @@ impl std::error::Error for UserInputHandlerError {
             UserInputHandlerError::ParseError(ref e) => Some(e),
             UserInputHandlerError::CommandMatchError(ref e) => Some(e),
             UserInputHandlerError::NotificationError(ref e) => Some(e),
+            UserInputHandlerError::DatabaseError(ref e) => Some(e),
+            UserInputHandlerError::HistoryError(ref e) => Some(e),
         }
     }
 }
@@ -0,0 +1,324 @@
+use chrono::{DateTime, Local, Utc};
+use serde_json::json;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::UnixDatagram;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+use crate::command::output::OutputFormat;
+use crate::command::util;
+use crate::ipc::{self, Bincodec, MessageResponse};
+use crate::notification::{Notification, NotificationState};
+
+pub type EventSender = UnboundedSender<Event>;
+pub type ArcSubscribers = Arc<Mutex<Vec<Subscriber>>>;
+
+/// How long a subscriber can hold back the event, e.g. a stopped process with its buffer full
+const SEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What happened to the notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Created,
+    WorkStarted,
+    WorkDone,
+    BreakDone,
+    Deleted,
+    Paused,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::WorkStarted => "work_started",
+            EventKind::WorkDone => "work_done",
+            EventKind::BreakDone => "break_done",
+            EventKind::Deleted => "deleted",
+            EventKind::Paused => "paused",
+        }
+    }
+}
+
+/// The event pushed to the subscribed uds clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    kind: EventKind,
+    id: u16,
+    description: String,
+    // work_time and break_time are in seconds
+    work_time: u32,
+    break_time: u32,
+    at: DateTime<Utc>,
+}
+
+impl Event {
+    pub fn new(kind: EventKind, notification: &Notification, at: DateTime<Utc>) -> Self {
+        let (id, description, work_time, break_time, _, _, _) = notification.get_values();
+
+        Event {
+            kind,
+            id,
+            description: description.to_string(),
+            work_time,
+            break_time,
+            at,
+        }
+    }
+
+    pub fn get_kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// render returns the line printed by the subscriber
+    pub fn render(&self, output: OutputFormat) -> String {
+        match output {
+            OutputFormat::Text => {
+                let description = if self.description.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", self.description)
+                };
+
+                format!(
+                    "[{}] Notification (id: {}) {}{}",
+                    self.at.with_timezone(&Local),
+                    self.id,
+                    self.kind.as_str().replace('_', " "),
+                    description
+                )
+            }
+            OutputFormat::Json => json!({
+                "event": self.kind.as_str(),
+                "id": self.id,
+                "description": self.description,
+                "work_time": self.work_time,
+                "break_time": self.break_time,
+                "at": util::to_rfc3339(self.at),
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// publish sends the event to the dispatcher. The event is dropped if nobody receives it,
+/// e.g. the server socket is owned by another pomodoro.
+pub fn publish(event_tx: &EventSender, event: Event) {
+    if let Err(e) = event_tx.send(event) {
+        debug!("event is not published: {:?}", e);
+    }
+}
+
+/// publish_work_started publishes the work started event if the work is running at the given time.
+/// It is for the notification starting right away, since its task publishes the event
+/// only after waiting for the start.
+pub fn publish_work_started(
+    event_tx: &EventSender,
+    notification: &Notification,
+    at: DateTime<Utc>,
+) {
+    if notification.get_state(at) == NotificationState::Work {
+        publish(
+            event_tx,
+            Event::new(EventKind::WorkStarted, notification, at),
+        );
+    }
+}
+
+/// A uds client which receives the events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscriber {
+    path: PathBuf,
+    output: OutputFormat,
+}
+
+impl Subscriber {
+    pub fn new(path: PathBuf, output: OutputFormat) -> Self {
+        Subscriber { path, output }
+    }
+}
+
+/// subscribe registers the subscriber. The one subscribed again from the same path is replaced.
+pub fn subscribe(subscribers: &ArcSubscribers, subscriber: Subscriber) {
+    let mut subscribers = subscribers.lock().unwrap();
+    subscribers.retain(|s| s.path != subscriber.path);
+    subscribers.push(subscriber);
+}
+
+/// spawn_event_dispatcher sends the published events to the subscribers.
+/// The event is sent to every subscriber at once, and the next one waits until all of them
+/// received it, so that the chunks of two events never interleave.
+/// Subscribers which can't receive the event in time, e.g. exited or stopped ones, are removed.
+/// The socket file left by an interrupted subscriber is removed as well.
+pub fn spawn_event_dispatcher(
+    mut event_rx: UnboundedReceiver<Event>,
+    socket: Arc<UnixDatagram>,
+    subscribers: ArcSubscribers,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(event) = event_rx.recv().await {
+            debug!("event: {:?}", event);
+            let targets = subscribers.lock().unwrap().clone();
+
+            let mut handles = Vec::with_capacity(targets.len());
+            for subscriber in targets {
+                let message =
+                    match MessageResponse::new(vec![event.render(subscriber.output)]).encode() {
+                        Ok(message) => message,
+                        Err(e) => {
+                            debug!("failed to encode the event: {:?}", e);
+                            continue;
+                        }
+                    };
+
+                let socket = socket.clone();
+                let subscribers = subscribers.clone();
+                handles.push(tokio::spawn(async move {
+                    send_event(&socket, &subscribers, subscriber, &message).await;
+                }));
+            }
+
+            for handle in handles {
+                if let Err(e) = handle.await {
+                    debug!("failed to send the event: {:?}", e);
+                }
+            }
+        }
+    })
+}
+
+/// send_event sends the encoded event to the subscriber, which is removed if it fails
+async fn send_event(
+    socket: &UnixDatagram,
+    subscribers: &ArcSubscribers,
+    subscriber: Subscriber,
+    message: &[u8],
+) {
+    let result = match timeout(
+        SEND_TIMEOUT,
+        ipc::send_to(socket, subscriber.path.clone(), message),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(_) => Err(io::Error::new(
+            ErrorKind::TimedOut,
+            "the subscriber does not receive the event",
+        )),
+    };
+
+    if let Err(e) = result {
+        debug!("subscriber {:?} is removed: {:?}", subscriber.path, e);
+        if e.kind() == ErrorKind::ConnectionRefused {
+            if let Err(e) = fs::remove_file(&subscriber.path) {
+                debug!("failed to remove the subscriber socket: {:?}", e);
+            }
+        }
+        subscribers
+            .lock()
+            .unwrap()
+            .retain(|s| s.path != subscriber.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
+
+    use super::{publish_work_started, subscribe, Event, EventKind, Subscriber};
+    use crate::command::output::OutputFormat;
+    use crate::notification::Notification;
+
+    #[test]
+    fn test_render() {
+        let at = Utc.ymd(2023, 3, 1).and_hms(9, 25, 0);
+        let notification = Notification::new(3, "review PR", 25 * 60, 5 * 60, at);
+
+        let event = Event::new(EventKind::WorkDone, &notification, at);
+        let text = event.render(OutputFormat::Text);
+        assert!(
+            text.ends_with("] Notification (id: 3) work done: review PR"),
+            "{}",
+            text
+        );
+
+        let json: Value = serde_json::from_str(&event.render(OutputFormat::Json)).unwrap();
+        assert_eq!(
+            json!({
+                "event": "work_done",
+                "id": 3,
+                "description": "review PR",
+                "work_time": 1500,
+                "break_time": 300,
+                "at": "2023-03-01T09:25:00Z",
+            }),
+            json
+        );
+
+        let notification = Notification::new(4, "", 25 * 60, 5 * 60, at);
+        let event = Event::new(EventKind::Created, &notification, at);
+        assert!(event
+            .render(OutputFormat::Text)
+            .ends_with("] Notification (id: 4) created"));
+    }
+
+    #[test]
+    fn test_publish_work_started() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let now = Utc::now();
+
+        publish_work_started(
+            &event_tx,
+            &Notification::new(0, "", 25 * 60, 5 * 60, now),
+            now,
+        );
+        let event = event_rx.try_recv().unwrap();
+        assert_eq!(EventKind::WorkStarted, event.get_kind());
+
+        // the task publishes it after waiting for the start
+        publish_work_started(
+            &event_tx,
+            &Notification::new(1, "", 25 * 60, 5 * 60, now + Duration::minutes(5)),
+            now,
+        );
+        // break only notification has no work
+        publish_work_started(&event_tx, &Notification::new(2, "", 0, 5 * 60, now), now);
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let subscribers = Arc::new(Mutex::new(Vec::new()));
+        let path = PathBuf::from("/tmp/rust-cli-pomodoro-subscriber-1.sock");
+
+        subscribe(
+            &subscribers,
+            Subscriber::new(path.clone(), OutputFormat::Text),
+        );
+        subscribe(
+            &subscribers,
+            Subscriber::new(
+                PathBuf::from("/tmp/rust-cli-pomodoro-subscriber-2.sock"),
+                OutputFormat::Text,
+            ),
+        );
+        // subscribed again with another format
+        subscribe(
+            &subscribers,
+            Subscriber::new(path.clone(), OutputFormat::Json),
+        );
+
+        let subscribers = subscribers.lock().unwrap();
+        assert_eq!(2, subscribers.len());
+        assert_eq!(Subscriber::new(path, OutputFormat::Json), subscribers[1]);
+    }
+}
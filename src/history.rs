@@ -0,0 +1,656 @@
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::command::util;
+use crate::error::HistoryError;
+use crate::notification::ArchivedNotification;
+
+const ICS_PRODID: &str = "-//rust-cli-pomodoro//pomodoro//EN";
+// lines of iCalendar should not be longer than 75 octets
+const ICS_LINE_LENGTH: usize = 75;
+
+/// The file format of the exported history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryFormat {
+    Csv,
+    Json,
+    Ics,
+}
+
+impl HistoryFormat {
+    /// from_path returns the format matching the extension of the path, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| ext.parse().ok())
+    }
+}
+
+impl FromStr for HistoryFormat {
+    type Err = HistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(HistoryFormat::Csv),
+            "json" => Ok(HistoryFormat::Json),
+            "ics" => Ok(HistoryFormat::Ics),
+            _ => Err(HistoryError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// A row of the exported history. Times are in seconds and timestamps are in RFC 3339
+/// with milliseconds, which are kept in the database, so that the imported rows match the history.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct HistoryRecord {
+    id: u16,
+    description: String,
+    work_time: u32,
+    break_time: u32,
+    started_at: String,
+    work_expired_at: String,
+    break_expired_at: String,
+    cut_short: bool,
+}
+
+impl From<&ArchivedNotification> for HistoryRecord {
+    fn from(n: &ArchivedNotification) -> Self {
+        HistoryRecord {
+            id: n.get_id(),
+            description: n.get_description().to_string(),
+            work_time: n.get_work_time(),
+            break_time: n.get_break_time(),
+            started_at: to_timestamp(n.get_start_at()),
+            work_expired_at: to_timestamp(n.get_work_expired_at()),
+            break_expired_at: to_timestamp(n.get_break_expired_at()),
+            cut_short: n.is_cut_short(),
+        }
+    }
+}
+
+fn to_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// export_history writes the archived notifications to the path and returns how many are written.
+pub fn export_history(
+    notifications: &[ArchivedNotification],
+    path: &Path,
+    format: HistoryFormat,
+    now: DateTime<Utc>,
+) -> Result<usize, HistoryError> {
+    let content = match format {
+        HistoryFormat::Csv => to_csv(notifications),
+        HistoryFormat::Json => to_json(notifications)?,
+        HistoryFormat::Ics => to_ics(notifications, now),
+    };
+
+    fs::write(path, content).map_err(HistoryError::Io)?;
+
+    Ok(notifications.len())
+}
+
+/// An archived notification read from an exported file
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImportedRecord {
+    description: String,
+    work_time: u32,
+    break_time: u32,
+    started_at: DateTime<Utc>,
+    cut_short: bool,
+}
+
+impl ImportedRecord {
+    pub fn get_description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    pub fn get_work_time(&self) -> u32 {
+        self.work_time
+    }
+
+    pub fn get_break_time(&self) -> u32 {
+        self.break_time
+    }
+
+    pub fn get_started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    pub fn is_cut_short(&self) -> bool {
+        self.cut_short
+    }
+}
+
+/// import_history reads the archived notifications from the path.
+/// Columns of other tools are also read if they are named like `start` or `duration`.
+pub fn import_history(
+    path: &Path,
+    format: HistoryFormat,
+) -> Result<Vec<ImportedRecord>, HistoryError> {
+    let content = fs::read_to_string(path).map_err(HistoryError::Io)?;
+
+    let rows = match format {
+        HistoryFormat::Csv => parse_csv(&content)?,
+        HistoryFormat::Json => parse_json(&content)?,
+        HistoryFormat::Ics => {
+            return Err(HistoryError::Unsupported(String::from(
+                "importing ics is not supported, use csv or json",
+            )))
+        }
+    };
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            parse_record(row).map_err(|reason| {
+                HistoryError::InvalidRecord(format!("record {}: {}", i + 1, reason))
+            })
+        })
+        .collect()
+}
+
+/// remove_duplicates removes the records already in the history or appearing twice,
+/// which have the same start time and description. Start times are compared in seconds,
+/// since the files exported by other tools usually have no milliseconds.
+pub fn remove_duplicates(
+    records: Vec<ImportedRecord>,
+    history: &[ArchivedNotification],
+) -> Vec<ImportedRecord> {
+    let mut keys = history
+        .iter()
+        .map(|n| {
+            (
+                n.get_start_at().trunc_subsecs(0),
+                n.get_description().to_string(),
+            )
+        })
+        .collect::<HashSet<(DateTime<Utc>, String)>>();
+
+    records
+        .into_iter()
+        .filter(|r| keys.insert((r.started_at.trunc_subsecs(0), r.description.clone())))
+        .collect()
+}
+
+/// get_field returns the first present value among the column names.
+fn get_field<'a>(row: &'a HashMap<String, String>, names: &[&str]) -> Option<&'a str> {
+    names
+        .iter()
+        .filter_map(|name| row.get(*name))
+        .map(|value| value.trim())
+        .find(|value| !value.is_empty())
+}
+
+fn parse_record(row: &HashMap<String, String>) -> Result<ImportedRecord, String> {
+    let parse_time = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|_| format!("'{}' is not a RFC 3339 timestamp", value))
+    };
+    // a number without unit is in seconds, like the exported one
+    let parse_seconds = |value: &str| util::parse_seconds(value).map_err(|e| e.to_string());
+
+    let started_at = get_field(row, &["started_at", "start_at", "start", "start_time"])
+        .ok_or_else(|| String::from("start time is missing"))
+        .and_then(parse_time)?;
+
+    let work_expired_at = get_field(row, &["work_expired_at", "end", "end_time"])
+        .map(parse_time)
+        .transpose()?;
+
+    let work_time = match get_field(row, &["work_time", "duration"]) {
+        Some(value) => parse_seconds(value)?,
+        None => match work_expired_at {
+            Some(t) if t >= started_at => u32::try_from((t - started_at).num_seconds())
+                .map_err(|_| String::from("work time is out of range"))?,
+            Some(_) => return Err(String::from("end time is before start time")),
+            None => return Err(String::from("work time is missing")),
+        },
+    };
+
+    let break_time = get_field(row, &["break_time"])
+        .map(parse_seconds)
+        .transpose()?
+        .unwrap_or(0);
+
+    if work_time == 0 && break_time == 0 {
+        return Err(String::from("both work time and break time are zero"));
+    }
+
+    let cut_short = match get_field(row, &["cut_short"]) {
+        Some(value) => value
+            .parse::<bool>()
+            .map_err(|_| format!("'{}' is not true or false", value))?,
+        None => false,
+    };
+
+    Ok(ImportedRecord {
+        description: get_field(row, &["description", "task"])
+            .unwrap_or_default()
+            .to_string(),
+        work_time,
+        break_time,
+        started_at,
+        cut_short,
+    })
+}
+
+/// parse_csv returns the rows keyed by the lowercased header.
+fn parse_csv(content: &str) -> Result<Vec<HashMap<String, String>>, HistoryError> {
+    let mut lines = split_csv(content)?.into_iter();
+    let header = match lines.next() {
+        Some(header) => header
+            .into_iter()
+            .map(|name| name.trim().to_lowercase())
+            .collect::<Vec<String>>(),
+        None => return Ok(Vec::new()),
+    };
+
+    Ok(lines
+        .filter(|fields| !(fields.len() == 1 && fields[0].is_empty()))
+        .map(|fields| header.iter().cloned().zip(fields).collect())
+        .collect())
+}
+
+/// split_csv splits the content into the fields of the lines, handling quoted fields.
+fn split_csv(content: &str) -> Result<Vec<Vec<String>>, HistoryError> {
+    let mut lines = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                lines.push(std::mem::take(&mut fields));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(HistoryError::InvalidRecord(String::from(
+            "unclosed quote in csv",
+        )));
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        lines.push(fields);
+    }
+
+    Ok(lines)
+}
+
+/// parse_json returns the objects of the array with their values as strings.
+fn parse_json(content: &str) -> Result<Vec<HashMap<String, String>>, HistoryError> {
+    let value: Value = serde_json::from_str(content).map_err(HistoryError::Json)?;
+    let objects = value.as_array().ok_or_else(|| {
+        HistoryError::InvalidRecord(String::from("json should be an array of objects"))
+    })?;
+
+    objects
+        .iter()
+        .enumerate()
+        .map(|(i, object)| {
+            let object = object.as_object().ok_or_else(|| {
+                HistoryError::InvalidRecord(format!("record {}: not an object", i + 1))
+            })?;
+
+            Ok(object
+                .iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        Value::Number(n) => n.to_string(),
+                        Value::Bool(b) => b.to_string(),
+                        _ => return None,
+                    };
+
+                    Some((key.to_lowercase(), value))
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn to_csv(notifications: &[ArchivedNotification]) -> String {
+    let mut lines = vec![String::from(
+        "id,description,work_time,break_time,started_at,work_expired_at,break_expired_at,cut_short",
+    )];
+
+    lines.extend(notifications.iter().map(HistoryRecord::from).map(|r| {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            r.id,
+            escape_csv(&r.description),
+            r.work_time,
+            r.break_time,
+            r.started_at,
+            r.work_expired_at,
+            r.break_expired_at,
+            r.cut_short
+        )
+    }));
+
+    lines.join("\n") + "\n"
+}
+
+/// escape_csv quotes the field if it has a comma, a quote or a line break.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_json(notifications: &[ArchivedNotification]) -> Result<String, HistoryError> {
+    let records = notifications
+        .iter()
+        .map(HistoryRecord::from)
+        .collect::<Vec<HistoryRecord>>();
+
+    serde_json::to_string_pretty(&records).map_err(HistoryError::Json)
+}
+
+/// to_ics returns the iCalendar in which each work phase is an event.
+fn to_ics(notifications: &[ArchivedNotification], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:{}", ICS_PRODID),
+    ];
+
+    for n in notifications.iter().filter(|n| n.get_work_time() > 0) {
+        let summary = match n.get_description() {
+            "" => "Pomodoro",
+            description => description,
+        };
+        let mut description = format!(
+            "work {}, break {}",
+            util::format_duration(n.get_work_time()),
+            util::format_duration(n.get_break_time())
+        );
+        if n.is_cut_short() {
+            description.push_str(", cut short");
+        }
+
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            format!(
+                "UID:{}-{}@rust-cli-pomodoro",
+                n.get_id(),
+                to_ics_time(n.get_start_at())
+            ),
+            format!("DTSTAMP:{}", to_ics_time(now)),
+            format!("DTSTART:{}", to_ics_time(n.get_start_at())),
+            format!("DTEND:{}", to_ics_time(n.get_work_expired_at())),
+            format!("SUMMARY:{}", escape_ics(summary)),
+            format!("DESCRIPTION:{}", escape_ics(&description)),
+            String::from("END:VEVENT"),
+        ]);
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines
+        .iter()
+        .map(|line| fold_ics_line(line) + "\r\n")
+        .collect()
+}
+
+fn to_ics_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// fold_ics_line splits the long line into lines, each following line starting with a space.
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > ICS_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // the leading space is counted
+            length = 1;
+        }
+
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use std::path::Path;
+
+    use super::{
+        escape_csv, escape_ics, fold_ics_line, parse_csv, parse_json, parse_record,
+        remove_duplicates, to_csv, to_ics, to_json, HistoryFormat, ImportedRecord,
+    };
+    use crate::notification::{ArchivedNotification, Notification};
+
+    fn get_notifications() -> Vec<ArchivedNotification> {
+        let start_at = Utc.ymd(2026, 10, 17).and_hms(9, 0, 0);
+        let mut cut_short = Notification::new(1, "review, \"PR\" #42", 25 * 60, 5 * 60, start_at);
+        cut_short.skip(start_at + chrono::Duration::minutes(10));
+
+        vec![
+            ArchivedNotification::from(Notification::new(0, "", 25 * 60, 5 * 60, start_at)),
+            ArchivedNotification::from(cut_short),
+            // snoozed break has no work phase
+            ArchivedNotification::from(Notification::new(2, "", 0, 5 * 60, start_at)),
+        ]
+    }
+
+    #[test]
+    fn test_history_format() {
+        assert_eq!(Ok(HistoryFormat::Csv), "CSV".parse().map_err(|_| ()));
+        assert!("xml".parse::<HistoryFormat>().is_err());
+
+        assert_eq!(
+            Some(HistoryFormat::Ics),
+            HistoryFormat::from_path(Path::new("/tmp/history.ics"))
+        );
+        assert_eq!(None, HistoryFormat::from_path(Path::new("history")));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = to_csv(&get_notifications());
+        let lines = csv.lines().collect::<Vec<&str>>();
+
+        assert_eq!(4, lines.len());
+        assert_eq!(
+            "0,,1500,300,2026-10-17T09:00:00.000Z,2026-10-17T09:25:00.000Z,2026-10-17T09:30:00.000Z,false",
+            lines[1]
+        );
+        assert_eq!(
+            "1,\"review, \"\"PR\"\" #42\",600,300,2026-10-17T09:00:00.000Z,2026-10-17T09:10:00.000Z,2026-10-17T09:15:00.000Z,true",
+            lines[2]
+        );
+        assert_eq!("plain", escape_csv("plain"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&get_notifications()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(3, value.as_array().unwrap().len());
+        assert_eq!("review, \"PR\" #42", value[1]["description"]);
+        assert_eq!(600, value[1]["work_time"]);
+        assert_eq!("2026-10-17T09:10:00.000Z", value[1]["work_expired_at"]);
+        assert_eq!(true, value[1]["cut_short"]);
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let now = Utc.ymd(2026, 10, 18).and_hms(0, 0, 0);
+        let ics = to_ics(&get_notifications(), now);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        // the break only notification is not an event
+        assert_eq!(2, ics.matches("BEGIN:VEVENT").count());
+        assert!(ics.contains("DTSTART:20261017T090000Z\r\nDTEND:20261017T092500Z\r\n"));
+        assert!(ics.contains("SUMMARY:Pomodoro\r\n"));
+        assert!(ics.contains("SUMMARY:review\\, \"PR\" #42\r\n"));
+        assert!(ics.contains("DESCRIPTION:work 10m\\, break 5m\\, cut short\r\n"));
+
+        // no bare carriage return is left to break the line
+        assert_eq!("a\\nb\\nc\\nd", escape_ics("a\r\nb\rc\nd"));
+    }
+
+    fn parse_records(rows: Vec<std::collections::HashMap<String, String>>) -> Vec<ImportedRecord> {
+        rows.iter().map(|row| parse_record(row).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_import_exported_history() {
+        let notifications = get_notifications();
+        let start_at = Utc.ymd(2026, 10, 17).and_hms(9, 0, 0);
+
+        for records in [
+            parse_records(parse_csv(&to_csv(&notifications)).unwrap()),
+            parse_records(parse_json(&to_json(&notifications).unwrap()).unwrap()),
+        ] {
+            assert_eq!(3, records.len());
+
+            assert_eq!("review, \"PR\" #42", records[1].get_description());
+            assert_eq!(600, records[1].get_work_time());
+            assert_eq!(300, records[1].get_break_time());
+            assert_eq!(start_at, records[1].get_started_at());
+            assert!(records[1].is_cut_short());
+
+            assert_eq!(0, records[2].get_work_time());
+        }
+    }
+
+    #[test]
+    fn test_import_other_history() {
+        let csv = "Start,End,Task\r\n2026-10-17T09:00:00+09:00,2026-10-17T09:50:00+09:00,\"write\ndocs\"\r\n";
+        let records = parse_records(parse_csv(csv).unwrap());
+
+        assert_eq!(1, records.len());
+        assert_eq!("write\ndocs", records[0].get_description());
+        assert_eq!(50 * 60, records[0].get_work_time());
+        assert_eq!(0, records[0].get_break_time());
+        assert_eq!(
+            Utc.ymd(2026, 10, 17).and_hms(0, 0, 0),
+            records[0].get_started_at()
+        );
+
+        let json = r#"[{"start": "2026-10-17T09:00:00Z", "duration": "25m", "break_time": 300.0}]"#;
+        let records = parse_records(parse_json(json).unwrap());
+        assert_eq!(25 * 60, records[0].get_work_time());
+        assert_eq!(300, records[0].get_break_time());
+    }
+
+    #[test]
+    fn test_import_invalid_history() {
+        let rows = parse_csv("description,work_time\nreview,1500\n").unwrap();
+        assert_eq!(
+            "start time is missing",
+            parse_record(&rows[0]).err().unwrap()
+        );
+
+        let rows = parse_csv("started_at,work_time\nyesterday,1500\n").unwrap();
+        assert!(parse_record(&rows[0]).err().unwrap().contains("RFC 3339"));
+
+        let rows = parse_csv("started_at,work_time\n2026-10-17T09:00:00Z,0\n").unwrap();
+        assert!(parse_record(&rows[0]).is_err());
+
+        assert!(parse_csv("started_at\n\"2026").is_err());
+        assert!(parse_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_remove_duplicates() {
+        let history = get_notifications();
+        let csv = "started_at,work_time,description
+2026-10-17T09:00:00Z,1500,
+2026-10-17T09:00:00Z,1500,another
+2026-10-17T10:00:00Z,1500,
+2026-10-17T10:00:00Z,1500,
+";
+        let records = parse_records(parse_csv(csv).unwrap());
+        let records = remove_duplicates(records, &history[..2]);
+
+        assert_eq!(2, records.len());
+        assert_eq!("another", records[0].get_description());
+        assert_eq!(
+            Utc.ymd(2026, 10, 17).and_hms(10, 0, 0),
+            records[1].get_started_at()
+        );
+    }
+
+    #[test]
+    fn test_reimport_exported_history() {
+        // the database keeps milliseconds of the timestamps
+        let start_at = Utc.ymd(2026, 10, 17).and_hms_milli(9, 0, 0, 123);
+        let history = vec![
+            ArchivedNotification::from(Notification::new(0, "", 25 * 60, 5 * 60, start_at)),
+            ArchivedNotification::from(Notification::new(
+                1,
+                "review",
+                25 * 60,
+                5 * 60,
+                start_at + chrono::Duration::milliseconds(30 * 60 * 1000 + 456),
+            )),
+        ];
+
+        for records in [
+            parse_records(parse_csv(&to_csv(&history)).unwrap()),
+            parse_records(parse_json(&to_json(&history).unwrap()).unwrap()),
+        ] {
+            assert_eq!(start_at, records[0].get_started_at());
+            assert!(remove_duplicates(records, &history).is_empty());
+        }
+
+        // exported without milliseconds by an older version
+        let csv = "started_at,work_time,description\n2026-10-17T09:00:00Z,1500,\n";
+        let records = parse_records(parse_csv(csv).unwrap());
+        assert!(remove_duplicates(records, &history).is_empty());
+    }
+
+    #[test]
+    fn test_fold_ics_line() {
+        let line = "a".repeat(80);
+        let folded = fold_ics_line(&line);
+
+        assert_eq!(format!("{}\r\n {}", "a".repeat(75), "a".repeat(5)), folded);
+        assert_eq!("short", fold_ics_line("short"));
+    }
+}
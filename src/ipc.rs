@@ -2,30 +2,41 @@ use bincode::error::DecodeError;
 use bincode::error::EncodeError;
 use bincode::Decode;
 use bincode::Encode;
+use serde_json::json;
+use std::collections::hash_map::RandomState;
 use std::env;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process;
 use std::time::Duration;
 use tokio::net::UnixDatagram;
 use tokio::time::{sleep, timeout};
 
 use crate::command::action::ActionType;
+use crate::command::output::OutputFormat;
+use crate::command::util;
 use crate::InputSource;
 use crate::UserInput;
 
+// the directory of the sockets in the runtime dir of the user
+const SOCKET_DIR: &str = "pomodoro";
 const SOCKET_SERVER_ADDR: &str = "rust-cli-pomodoro-server.sock";
-const SOCKET_CLIENT_ADDR: &str = "rust-cli-pomodoro-client.sock";
+// each client binds its own socket, named with the process id,
+// so that the clients running at the same time get their own responses
+const SOCKET_CLIENT_PREFIX: &str = "rust-cli-pomodoro-client-";
 
-const CHUNK: usize = 2048;
-
-pub enum UdsType {
-    Server,
-    Client,
-}
+// the size of the datagrams which a response is split into
+pub const CHUNK: usize = 2048;
+// a request is sent in a single datagram, and the longer one is truncated when received
+pub const REQUEST_BUFFER_LENGTH: usize = 64 * 1024;
 
 #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Eq)]
 pub enum UdsMessage {
-    Public(MessageRequest),
+    Public(MessageRequest, OutputFormat),
     Internal(internal::Message),
 }
 
@@ -57,27 +68,74 @@ pub trait Bincodec {
     }
 }
 
+/// Times of the requests are in seconds
 #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Eq)]
 pub enum MessageRequest {
     Create {
-        work: Option<u16>,
-        r#break: Option<u16>,
+        work: Option<u32>,
+        r#break: Option<u32>,
+        message: Option<String>,
+        at: Option<String>,
+        r#in: Option<u32>,
+        tz: Option<String>,
     },
     Queue {
-        work: Option<u16>,
-        r#break: Option<u16>,
+        work: Option<u32>,
+        r#break: Option<u32>,
+        message: Option<String>,
+    },
+    Cycle {
+        work: Option<u32>,
+        r#break: Option<u32>,
+        long_break: Option<u32>,
+        count: Option<u16>,
+        message: Option<String>,
     },
     Delete {
         id: u16,
         all: bool,
     },
+    Pause {
+        id: u16,
+    },
+    Resume {
+        id: u16,
+    },
+    Extend {
+        id: u16,
+        seconds: u32,
+    },
+    Snooze {
+        id: u16,
+        seconds: u32,
+    },
+    Skip {
+        id: u16,
+        silent: bool,
+    },
     List {
         show_percentage: bool,
+        tz: Option<String>,
     },
     Test,
     History {
         should_clear: bool,
+        tz: Option<String>,
+        export: Option<String>,
+        import: Option<String>,
+        format: Option<String>,
+    },
+    Stats {
+        days: Option<u16>,
+        tz: Option<String>,
+        heatmap: bool,
+        plain: bool,
     },
+    Status {
+        format: Option<String>,
+    },
+    // handled by the uds input handler, which keeps the client to push the events
+    Subscribe,
 }
 
 impl Bincodec for MessageRequest {
@@ -87,28 +145,48 @@ impl Bincodec for MessageRequest {
 impl From<MessageRequest> for UserInput {
     fn from(request: MessageRequest) -> Self {
         let input = match request {
-            MessageRequest::Create { work, r#break } => {
-                let mut data = format!("{} ", String::from(ActionType::Create));
-
-                if let Some(val) = work {
-                    data.push_str(&format!("-w {} ", val))
+            MessageRequest::Create {
+                work,
+                r#break,
+                message,
+                at,
+                r#in,
+                tz,
+            } => {
+                let mut data = get_create_input(ActionType::Create, work, r#break, message);
+
+                if let Some(val) = at {
+                    data.push_str(&format!(" --at {}", util::quote_arg(&val)))
                 }
 
-                if let Some(val) = r#break {
-                    data.push_str(&format!("-b {}", val))
+                if let Some(val) = r#in {
+                    data.push_str(&format!(" --in {}s", val))
                 }
 
+                push_tz_arg(&mut data, tz);
+
                 data
             }
-            MessageRequest::Queue { work, r#break } => {
-                let mut data = format!("{} ", String::from(ActionType::Queue));
-
-                if let Some(val) = work {
-                    data.push_str(&format!("-w {} ", val))
+            MessageRequest::Queue {
+                work,
+                r#break,
+                message,
+            } => get_create_input(ActionType::Queue, work, r#break, message),
+            MessageRequest::Cycle {
+                work,
+                r#break,
+                long_break,
+                count,
+                message,
+            } => {
+                let mut data = get_create_input(ActionType::Cycle, work, r#break, message);
+
+                if let Some(val) = long_break {
+                    data.push_str(&format!(" -l {}s", val))
                 }
 
-                if let Some(val) = r#break {
-                    data.push_str(&format!("-b {}", val))
+                if let Some(val) = count {
+                    data.push_str(&format!(" -n {}", val))
                 }
 
                 data
@@ -120,21 +198,104 @@ impl From<MessageRequest> for UserInput {
                     format!("{} -i {}", String::from(ActionType::Delete), id)
                 }
             }
-            MessageRequest::List { show_percentage } => {
-                if show_percentage {
+            MessageRequest::Pause { id } => {
+                format!("{} -i {}", String::from(ActionType::Pause), id)
+            }
+            MessageRequest::Resume { id } => {
+                format!("{} -i {}", String::from(ActionType::Resume), id)
+            }
+            MessageRequest::Extend { id, seconds } => format!(
+                "{} -i {} -m {}s",
+                String::from(ActionType::Extend),
+                id,
+                seconds
+            ),
+            MessageRequest::Snooze { id, seconds } => format!(
+                "{} -i {} -m {}s",
+                String::from(ActionType::Snooze),
+                id,
+                seconds
+            ),
+            MessageRequest::Skip { id, silent } => {
+                if silent {
+                    format!("{} -i {} --silent", String::from(ActionType::Skip), id)
+                } else {
+                    format!("{} -i {}", String::from(ActionType::Skip), id)
+                }
+            }
+            MessageRequest::List {
+                show_percentage,
+                tz,
+            } => {
+                let mut data = if show_percentage {
                     format!("{} -p", String::from(ActionType::List))
                 } else {
                     String::from(ActionType::List)
-                }
+                };
+                push_tz_arg(&mut data, tz);
+
+                data
             }
             MessageRequest::Test => String::from(ActionType::Test),
-            MessageRequest::History { should_clear } => {
-                if should_clear {
+            MessageRequest::History {
+                should_clear,
+                tz,
+                export,
+                import,
+                format,
+            } => {
+                let mut data = if should_clear {
                     format!("{} --clear", String::from(ActionType::History))
                 } else {
                     String::from(ActionType::History)
+                };
+                push_tz_arg(&mut data, tz);
+
+                if let Some(val) = export {
+                    data.push_str(&format!(" --export {}", util::quote_arg(&val)))
+                }
+
+                if let Some(val) = import {
+                    data.push_str(&format!(" --import {}", util::quote_arg(&val)))
+                }
+
+                if let Some(val) = format {
+                    data.push_str(&format!(" --format {}", val))
                 }
+
+                data
+            }
+            MessageRequest::Stats {
+                days,
+                tz,
+                heatmap,
+                plain,
+            } => {
+                let mut data = String::from(ActionType::Stats);
+                if let Some(val) = days {
+                    data.push_str(&format!(" -d {}", val))
+                }
+                push_tz_arg(&mut data, tz);
+
+                if heatmap {
+                    data.push_str(" --heatmap");
+
+                    if plain {
+                        data.push_str(" --plain")
+                    }
+                }
+
+                data
             }
+            MessageRequest::Subscribe => String::from(ActionType::Subscribe),
+            MessageRequest::Status { format } => match format {
+                Some(val) => format!(
+                    "{} --format {}",
+                    String::from(ActionType::Status),
+                    util::quote_arg(&val)
+                ),
+                None => String::from(ActionType::Status),
+            },
         };
 
         debug!("input: {:?}", input);
@@ -142,10 +303,41 @@ impl From<MessageRequest> for UserInput {
         UserInput {
             input,
             source: InputSource::UnixDomainSocket,
+            output: OutputFormat::Text,
+            reply_to: None,
         }
     }
 }
 
+fn get_create_input(
+    action: ActionType,
+    work: Option<u32>,
+    r#break: Option<u32>,
+    message: Option<String>,
+) -> String {
+    let mut data = String::from(action);
+
+    if let Some(val) = work {
+        data.push_str(&format!(" -w {}s", val))
+    }
+
+    if let Some(val) = r#break {
+        data.push_str(&format!(" -b {}s", val))
+    }
+
+    if let Some(val) = message {
+        data.push_str(&format!(" -m {}", util::quote_arg(&val)))
+    }
+
+    data
+}
+
+fn push_tz_arg(data: &mut String, tz: Option<String>) {
+    if let Some(val) = tz {
+        data.push_str(&format!(" --tz {}", util::quote_arg(&val)))
+    }
+}
+
 #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Eq)]
 pub struct MessageResponse {
     body: Vec<String>,
@@ -156,6 +348,16 @@ impl MessageResponse {
         MessageResponse { body }
     }
 
+    /// error returns the response of the failed request in the requested format
+    pub fn error(message: String, output: OutputFormat) -> Self {
+        let body = match output {
+            OutputFormat::Text => message,
+            OutputFormat::Json => json!({ "error": message }).to_string(),
+        };
+
+        MessageResponse { body: vec![body] }
+    }
+
     pub fn get_body(&self) -> &Vec<String> {
         &self.body
     }
@@ -174,7 +376,7 @@ pub mod internal {
     use bincode::error::{DecodeError, EncodeError};
     use tokio::net::UnixDatagram;
 
-    use crate::command::handler::uds_client::BUFFER_LENGTH;
+    use super::CHUNK;
 
     #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Eq)]
     pub enum Message {
@@ -202,9 +404,9 @@ pub mod internal {
     ) -> Result<Message, Box<dyn std::error::Error>> {
         let mut vec = Vec::new();
         let mut total_size = 0;
+        let mut buf = vec![0u8; CHUNK];
 
         loop {
-            let mut buf = vec![0u8; BUFFER_LENGTH];
             let (size, _) = socket.recv_from(&mut buf).await?;
 
             let dgram = &buf[..size];
@@ -229,9 +431,9 @@ pub mod internal {
 }
 
 // TODO(young): The result should be optional
-pub async fn create_server_uds() -> Result<Option<UnixDatagram>, std::io::Error> {
+pub async fn create_server_uds(server_addr: &Path) -> Result<Option<UnixDatagram>, std::io::Error> {
     debug!("create_server_uds called");
-    let result = detect_address_in_use().await;
+    let result = detect_address_in_use(server_addr).await;
     debug!("result: {:?}", result);
     if let Ok(address_in_use) = result {
         if address_in_use {
@@ -241,28 +443,95 @@ pub async fn create_server_uds() -> Result<Option<UnixDatagram>, std::io::Error>
     }
 
     // TODO(young): handle create_uds_address error
-    let server_addr = create_uds_address(UdsType::Server, true)?;
-    let socket = UnixDatagram::bind(server_addr)?;
+    create_uds_address(server_addr, true)?;
+    let socket = bind_server_uds(server_addr)?;
+    #[cfg(target_os = "linux")]
+    credentials::enable(&socket)?;
 
     debug!("create_server_uds called");
     Ok(Some(socket))
 }
 
-pub async fn create_client_uds() -> Result<UnixDatagram, std::io::Error> {
-    let server_addr = create_uds_address(UdsType::Server, false)?;
-    let client_addr = create_uds_address(UdsType::Client, true)?;
-
-    let socket = UnixDatagram::bind(client_addr)?;
-    socket.connect(server_addr)?;
+/// create_client_uds binds the client socket at an address of its own and returns both
+pub async fn create_client_uds(
+    server_addr: &Path,
+) -> Result<(UnixDatagram, PathBuf), std::io::Error> {
+    let client_addr = get_client_address(server_addr, process::id(), get_nonce());
+    create_uds_address(&client_addr, true)?;
+
+    let socket = bind_uds(&client_addr)?;
+    if let Err(e) = socket.connect(server_addr) {
+        fs::remove_file(client_addr)?;
+        return Err(e);
+    }
 
     debug!("create_client_uds called");
+    Ok((socket, client_addr))
+}
+
+/// bind_uds binds the socket which only the user can send to.
+/// It is meant for the directory which only the user can access,
+/// since the socket file has the permissions by the umask until they are narrowed.
+fn bind_uds(path: &Path) -> Result<UnixDatagram, std::io::Error> {
+    let socket = UnixDatagram::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+
     Ok(socket)
 }
 
-async fn detect_address_in_use() -> Result<bool, std::io::Error> {
+/// bind_server_uds binds the server socket which only the user can send to.
+/// Out of a private directory, e.g. with `--socket`, the socket is bound in a private directory
+/// next to the path and moved to it, so that there is no moment when others can send to it.
+fn bind_server_uds(path: &Path) -> Result<UnixDatagram, std::io::Error> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+    if is_private_dir(dir) {
+        return bind_uds(path);
+    }
+
+    let private_dir = dir.join(format!(".{}-{:016x}", SOCKET_DIR, get_nonce()));
+    fs::DirBuilder::new().mode(0o700).create(&private_dir)?;
+
+    let private_path = private_dir.join(SOCKET_SERVER_ADDR);
+    let result = bind_uds(&private_path).and_then(|socket| {
+        fs::rename(&private_path, path)?;
+        Ok(socket)
+    });
+    if let Err(e) = fs::remove_dir_all(&private_dir) {
+        debug!("failed to remove the private directory: {:?}", e);
+    }
+
+    result
+}
+
+/// is_private_dir checks whether only the user can access the directory
+fn is_private_dir(dir: &Path) -> bool {
+    fs::metadata(dir).map_or(false, |metadata| {
+        metadata.uid() == get_uid() && metadata.mode() & 0o077 == 0
+    })
+}
+
+/// remove_client_uds removes the socket file of the client, which is not removed on drop
+pub fn remove_client_uds(client_addr: &Path) -> Result<(), std::io::Error> {
+    if client_addr.exists() {
+        fs::remove_file(client_addr)?;
+    }
+
+    Ok(())
+}
+
+async fn detect_address_in_use(server_addr: &Path) -> Result<bool, std::io::Error> {
     debug!("detect_address_in_use called");
-    let socket = create_client_uds().await?;
+    let (socket, client_addr) = create_client_uds(server_addr).await?;
+    let address_in_use = ping_server(socket).await;
+    if let Err(e) = remove_client_uds(&client_addr) {
+        debug!("failed to remove the client socket: {:?}", e);
+    }
+
+    Ok(address_in_use)
+}
 
+/// ping_server checks whether a server answers on the other end of the socket
+async fn ping_server(socket: UnixDatagram) -> bool {
     // TODO(young): Force `send` must get UdsMessage type
     let timeout_result = timeout(
         Duration::from_millis(500),
@@ -288,45 +557,255 @@ async fn detect_address_in_use() -> Result<bool, std::io::Error> {
             debug!("message_result: {:?}", message_result);
             if let Ok(msg) = message_result {
                 if msg == internal::Message::Pong {
-                    return Ok(true);
+                    return true;
                 }
             }
         }
         Err(err) => {
             debug!("did not receive value within 500 ms, {:?}", err);
-            return Ok(false);
+            return false;
         }
     }
 
-    Ok(false)
+    false
 }
 
-fn create_uds_address(r#type: UdsType, should_remove: bool) -> std::io::Result<PathBuf> {
-    let path = get_uds_address(r#type);
-
+fn create_uds_address(path: &Path, should_remove: bool) -> std::io::Result<()> {
     if should_remove && path.exists() {
-        debug!("path {:?} exists, remove it before binding", &path);
-        fs::remove_file(&path)?;
+        debug!("path {:?} exists, remove it before binding", path);
+        fs::remove_file(path)?;
     }
 
     debug!("create_uds_address, path: {:?}", path);
 
-    Ok(path)
+    Ok(())
 }
 
-pub fn get_uds_address(r#type: UdsType) -> PathBuf {
-    let socket_addr = match r#type {
-        UdsType::Server => SOCKET_SERVER_ADDR,
-        UdsType::Client => SOCKET_CLIENT_ADDR,
-    };
+/// get_server_address returns the path of the server socket, which is the `--socket` arg if given.
+/// Otherwise it is placed in `$XDG_RUNTIME_DIR/pomodoro`, or in the directory of the user
+/// in the temp dir, which is created so that only the user can access it.
+pub fn get_server_address(socket: Option<&String>) -> std::io::Result<PathBuf> {
+    if let Some(path) = socket {
+        // the client socket is bound next to it, so it must not depend on the working directory
+        return Ok(env::current_dir()?.join(path));
+    }
 
-    let mut p = env::temp_dir();
-    p.push(socket_addr);
+    let uid = get_uid();
+    let dir = get_socket_dir(env::var_os("XDG_RUNTIME_DIR"), uid);
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&dir)?;
+
+    // the directory in the temp dir may be created by another user beforehand
+    let metadata = fs::metadata(&dir)?;
+    if metadata.uid() != uid {
+        return Err(std::io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("socket directory {:?} is owned by another user", dir),
+        ));
+    }
+    if metadata.mode() & 0o077 != 0 {
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    }
+
+    Ok(dir.join(SOCKET_SERVER_ADDR))
+}
+
+fn get_socket_dir(runtime_dir: Option<OsString>, uid: u32) -> PathBuf {
+    match runtime_dir {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join(SOCKET_DIR),
+        _ => env::temp_dir().join(format!("{}-{}", SOCKET_DIR, uid)),
+    }
+}
+
+/// get_client_address returns the socket of the client of the process, next to the server socket.
+/// The nonce keeps a client which gets the pid of an exited one from taking over its address.
+pub fn get_client_address(server_addr: &Path, pid: u32, nonce: u64) -> PathBuf {
+    server_addr.with_file_name(format!(
+        "{}{}-{:016x}.sock",
+        SOCKET_CLIENT_PREFIX, pid, nonce
+    ))
+}
+
+/// get_nonce returns a random number, seeded by the os like the keys of a HashMap
+fn get_nonce() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// is_client_address checks whether the request is from a client of any process
+pub fn is_client_address(server_addr: &Path, path: &Path) -> bool {
+    path.parent() == server_addr.parent()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| name.starts_with(SOCKET_CLIENT_PREFIX))
+}
+
+/// A datagram received by the server with its sender
+#[derive(Debug)]
+pub struct Request {
+    pub size: usize,
+    // None if the sender is not bound to a path, e.g. unnamed socket
+    pub path: Option<PathBuf>,
+    // the user id of the sender process, or None if it is unknown
+    pub uid: Option<u32>,
+}
+
+impl Request {
+    /// is_same_user checks whether the sender runs as the same user,
+    /// so that other users can't control the pomodoro
+    pub fn is_same_user(&self) -> bool {
+        self.uid == Some(get_uid())
+    }
+}
+
+/// recv_request receives the datagram with the credentials which the kernel attaches,
+/// so that the sender can't pretend to be another user.
+#[cfg(target_os = "linux")]
+pub async fn recv_request(
+    socket: &UnixDatagram,
+    buf: &mut [u8],
+) -> Result<Request, std::io::Error> {
+    use std::os::unix::io::AsRawFd;
+    use tokio::io::Interest;
+
+    loop {
+        socket.readable().await?;
+
+        match socket.try_io(Interest::READABLE, || {
+            credentials::recv(socket.as_raw_fd(), buf)
+        }) {
+            Err(e) if e.kind() == ErrorKind::WouldBlock => continue,
+            result => return result,
+        }
+    }
+}
+
+/// recv_request receives the datagram. Credentials are not passed with datagrams here,
+/// so the owner of the socket file the datagram is sent from is regarded as the sender.
+#[cfg(not(target_os = "linux"))]
+pub async fn recv_request(
+    socket: &UnixDatagram,
+    buf: &mut [u8],
+) -> Result<Request, std::io::Error> {
+    let (size, addr) = socket.recv_from(buf).await?;
+    let path = addr.as_pathname().map(Path::to_path_buf);
+    let uid = path
+        .as_ref()
+        .and_then(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.uid());
+
+    Ok(Request { size, path, uid })
+}
+
+#[cfg(target_os = "linux")]
+mod credentials {
+    use std::ffi::OsString;
+    use std::io::Error;
+    use std::mem;
+    use std::os::raw::{c_int, c_void};
+    use std::os::unix::ffi::OsStringExt;
+    use std::os::unix::io::RawFd;
+    use std::path::PathBuf;
+    use tokio::net::UnixDatagram;
+
+    use super::Request;
+
+    /// enable makes the kernel attach the credentials of the sender to every datagram
+    pub fn enable(socket: &UnixDatagram) -> Result<(), Error> {
+        use std::os::unix::io::AsRawFd;
+
+        let on: c_int = 1;
+        let result = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_PASSCRED,
+                &on as *const c_int as *const c_void,
+                mem::size_of::<c_int>() as libc::socklen_t,
+            )
+        };
+
+        if result < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// recv receives a datagram with recvmsg and reads the uid from SCM_CREDENTIALS
+    pub fn recv(fd: RawFd, buf: &mut [u8]) -> Result<Request, Error> {
+        let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut c_void,
+            iov_len: buf.len(),
+        };
+        // u64 keeps the control messages aligned, and 64 bytes are enough for a ucred
+        let mut control = [0u64; 8];
+
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut addr as *mut libc::sockaddr_un as *mut c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_un>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        let size = unsafe { libc::recvmsg(fd, &mut msg, 0) };
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let mut uid = None;
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == libc::SOL_SOCKET
+                    && (*cmsg).cmsg_type == libc::SCM_CREDENTIALS
+                {
+                    let ucred = (libc::CMSG_DATA(cmsg) as *const libc::ucred).read_unaligned();
+                    uid = Some(ucred.uid);
+                }
+                cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+            }
+        }
+
+        Ok(Request {
+            size: size as usize,
+            path: get_pathname(&addr, msg.msg_namelen),
+            uid,
+        })
+    }
 
-    p
+    /// get_pathname returns the path of the address, or None for unnamed and abstract ones
+    fn get_pathname(addr: &libc::sockaddr_un, len: libc::socklen_t) -> Option<PathBuf> {
+        let offset = mem::size_of::<libc::sa_family_t>();
+        let len = (len as usize).checked_sub(offset)?.min(addr.sun_path.len());
+        let bytes = addr.sun_path[..len]
+            .iter()
+            .map(|c| *c as u8)
+            .take_while(|c| *c != 0)
+            .collect::<Vec<u8>>();
+
+        if bytes.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(OsString::from_vec(bytes)))
+        }
+    }
 }
 
-pub async fn send_to(socket: &UnixDatagram, target: PathBuf, buf: &[u8]) {
+fn get_uid() -> u32 {
+    // getuid is always successful
+    unsafe { libc::getuid() }
+}
+
+pub async fn send_to(
+    socket: &UnixDatagram,
+    target: PathBuf,
+    buf: &[u8],
+) -> Result<(), std::io::Error> {
     let size = buf.len();
     debug!("buf length: {}", size);
     debug!("size / CHUNK: {}", size / CHUNK);
@@ -337,7 +816,7 @@ pub async fn send_to(socket: &UnixDatagram, target: PathBuf, buf: &[u8]) {
 
         let buf = &buf[start..end];
         debug!("buf length to be sent: {}", buf.len());
-        socket.send_to(buf, &target).await.unwrap();
+        socket.send_to(buf, &target).await?;
 
         // Wait for certain time due to
         // "No buffer space available" error in mac os.
@@ -346,5 +825,325 @@ pub async fn send_to(socket: &UnixDatagram, target: PathBuf, buf: &[u8]) {
     }
 
     let fin = Vec::new();
-    socket.send_to(fin.as_slice(), &target).await.unwrap();
+    socket.send_to(fin.as_slice(), &target).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+    use std::process;
+    use tokio::net::UnixDatagram;
+
+    use super::{
+        bind_server_uds, bind_uds, get_client_address, get_socket_dir, is_client_address,
+        recv_request, Bincodec, MessageRequest, UdsMessage,
+    };
+    use crate::command::output::OutputFormat;
+    use crate::command::util;
+    use crate::UserInput;
+
+    #[test]
+    fn test_create_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Create {
+            work: Some(1500),
+            r#break: None,
+            message: Some(String::from("review \"PR\" #42")),
+            at: None,
+            r#in: None,
+            tz: None,
+        });
+        let args = util::split_input(&user_input.input).unwrap();
+        assert_eq!(
+            vec!["create", "-w", "1500s", "-m", "review \"PR\" #42"],
+            args
+        );
+
+        let user_input = UserInput::from(MessageRequest::Create {
+            work: None,
+            r#break: None,
+            message: None,
+            at: Some(String::from("2026-10-18 09:30")),
+            r#in: None,
+            tz: Some(String::from("America/New_York")),
+        });
+        let args = util::split_input(&user_input.input).unwrap();
+        assert_eq!(
+            vec![
+                "create",
+                "--at",
+                "2026-10-18 09:30",
+                "--tz",
+                "America/New_York"
+            ],
+            args
+        );
+
+        let user_input = UserInput::from(MessageRequest::Create {
+            work: None,
+            r#break: None,
+            message: None,
+            at: None,
+            r#in: Some(1200),
+            tz: None,
+        });
+        assert_eq!("create --in 1200s", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Queue {
+            work: None,
+            r#break: None,
+            message: None,
+        });
+        assert_eq!("queue", user_input.input);
+    }
+
+    #[test]
+    fn test_cycle_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Cycle {
+            work: Some(1500),
+            r#break: Some(300),
+            long_break: Some(1200),
+            count: Some(4),
+            message: None,
+        });
+        assert_eq!("cycle -w 1500s -b 300s -l 1200s -n 4", user_input.input);
+    }
+
+    #[test]
+    fn test_pause_and_resume_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Pause { id: 3 });
+        assert_eq!("pause -i 3", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Resume { id: 3 });
+        assert_eq!("resume -i 3", user_input.input);
+    }
+
+    #[test]
+    fn test_extend_and_snooze_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Extend {
+            id: 3,
+            seconds: 300,
+        });
+        assert_eq!("extend -i 3 -m 300s", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Snooze { id: 3, seconds: 45 });
+        assert_eq!("snooze -i 3 -m 45s", user_input.input);
+    }
+
+    #[test]
+    fn test_skip_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Skip {
+            id: 3,
+            silent: false,
+        });
+        assert_eq!("skip -i 3", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Skip {
+            id: 3,
+            silent: true,
+        });
+        assert_eq!("skip -i 3 --silent", user_input.input);
+    }
+
+    #[test]
+    fn test_list_and_history_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::List {
+            show_percentage: true,
+            tz: Some(String::from("Asia/Seoul")),
+        });
+        let args = util::split_input(&user_input.input).unwrap();
+        assert_eq!(vec!["list", "-p", "--tz", "Asia/Seoul"], args);
+
+        let user_input = UserInput::from(MessageRequest::History {
+            should_clear: false,
+            tz: None,
+            export: None,
+            import: None,
+            format: None,
+        });
+        assert_eq!("history", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::History {
+            should_clear: false,
+            tz: None,
+            export: Some(String::from("/tmp/my history.ics")),
+            import: None,
+            format: Some(String::from("ics")),
+        });
+        let args = util::split_input(&user_input.input).unwrap();
+        assert_eq!(
+            vec![
+                "history",
+                "--export",
+                "/tmp/my history.ics",
+                "--format",
+                "ics"
+            ],
+            args
+        );
+
+        let user_input = UserInput::from(MessageRequest::History {
+            should_clear: false,
+            tz: None,
+            export: None,
+            import: Some(String::from("/tmp/history.json")),
+            format: None,
+        });
+        assert_eq!("history --import \"/tmp/history.json\"", user_input.input);
+    }
+
+    #[test]
+    fn test_stats_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Stats {
+            days: Some(30),
+            tz: None,
+            heatmap: false,
+            plain: false,
+        });
+        assert_eq!("stats -d 30", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Stats {
+            days: None,
+            tz: None,
+            heatmap: true,
+            plain: true,
+        });
+        assert_eq!("stats --heatmap --plain", user_input.input);
+    }
+
+    #[test]
+    fn test_status_request_into_user_input() {
+        let user_input = UserInput::from(MessageRequest::Status { format: None });
+        assert_eq!("status", user_input.input);
+
+        let user_input = UserInput::from(MessageRequest::Status {
+            format: Some(String::from("{phase} {remaining}")),
+        });
+        assert_eq!("status --format \"{phase} {remaining}\"", user_input.input);
+        assert_eq!(
+            vec!["status", "--format", "{phase} {remaining}"],
+            util::split_input(&user_input.input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_client_address() {
+        let server_addr = Path::new("/run/user/1000/pomodoro/rust-cli-pomodoro-server.sock");
+
+        let client_addr = get_client_address(server_addr, 42, 0xbeef);
+        assert_eq!(
+            Path::new("/run/user/1000/pomodoro/rust-cli-pomodoro-client-42-000000000000beef.sock"),
+            client_addr
+        );
+        assert_ne!(client_addr, get_client_address(server_addr, 42, 0xcafe));
+        assert!(is_client_address(server_addr, &client_addr));
+        assert!(!is_client_address(server_addr, server_addr));
+        assert!(!is_client_address(
+            server_addr,
+            Path::new("/tmp/rust-cli-pomodoro-client-42.sock")
+        ));
+    }
+
+    #[test]
+    fn test_get_socket_dir() {
+        assert_eq!(
+            PathBuf::from("/run/user/1000/pomodoro"),
+            get_socket_dir(Some(OsString::from("/run/user/1000")), 1000)
+        );
+        assert_eq!(
+            env::temp_dir().join("pomodoro-1000"),
+            get_socket_dir(None, 1000)
+        );
+        assert_eq!(
+            env::temp_dir().join("pomodoro-1000"),
+            get_socket_dir(Some(OsString::new()), 1000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bind_uds() {
+        let path = env::temp_dir().join(format!("rust-cli-pomodoro-test-{}.sock", process::id()));
+        let _socket = bind_uds(&path).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(0o600, mode & 0o777);
+    }
+
+    #[tokio::test]
+    async fn test_bind_server_uds() {
+        // the temp dir is accessible by others, so it is bound in a private directory and moved
+        let path = env::temp_dir().join(format!(
+            "rust-cli-pomodoro-test-server-uds-{}.sock",
+            process::id()
+        ));
+        let server = bind_server_uds(&path).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let client = UnixDatagram::unbound().unwrap();
+        client.send_to(b"ping", &path).await.unwrap();
+        let mut buf = vec![0u8; 16];
+        let size = server.recv(&mut buf).await.unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(0o600, mode & 0o777);
+        assert_eq!(b"ping", &buf[..size]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_recv_request() {
+        let dir = env::temp_dir();
+        let server_path = dir.join(format!(
+            "rust-cli-pomodoro-test-server-{}.sock",
+            process::id()
+        ));
+        let client_path = dir.join(format!(
+            "rust-cli-pomodoro-test-client-{}.sock",
+            process::id()
+        ));
+
+        let server = bind_uds(&server_path).unwrap();
+        super::credentials::enable(&server).unwrap();
+        let client = bind_uds(&client_path).unwrap();
+        client.send_to(b"ping", &server_path).await.unwrap();
+
+        let mut buf = vec![0u8; 16];
+        let request = recv_request(&server, &mut buf).await;
+        fs::remove_file(&server_path).unwrap();
+        fs::remove_file(&client_path).unwrap();
+
+        let request = request.unwrap();
+        assert_eq!(b"ping", &buf[..request.size]);
+        assert_eq!(Some(client_path), request.path);
+        assert!(request.is_same_user());
+    }
+
+    #[test]
+    fn test_uds_message_codec() {
+        let message = UdsMessage::Public(
+            MessageRequest::List {
+                show_percentage: true,
+                tz: None,
+            },
+            OutputFormat::Json,
+        );
+        let encoded = message.encode().unwrap();
+
+        assert_eq!(
+            UdsMessage::Public(
+                MessageRequest::List {
+                    show_percentage: true,
+                    tz: None,
+                },
+                OutputFormat::Json,
+            ),
+            UdsMessage::decode(&encoded).unwrap()
+        );
+    }
 }
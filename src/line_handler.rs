@@ -1,3 +1,4 @@
+use crate::command::output::OutputFormat;
 use crate::{InputSource, UserInput};
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
@@ -29,6 +30,8 @@ pub fn handle(tx: Sender<UserInput>) -> JoinHandle<()> {
                         .send(UserInput {
                             input: line,
                             source: InputSource::StandardInput,
+                            output: OutputFormat::Text,
+                            reply_to: None,
                         })
                         .await;
                 }
@@ -1,10 +1,12 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use clap_complete::generate;
-use gluesql::prelude::{Glue, MemoryStorage};
+use serde_json::json;
 use std::collections::HashMap;
 use std::error::Error;
 use std::io::{self};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::time::sleep;
 use tokio::{net::UnixDatagram, sync::mpsc};
 use tokio::{sync::mpsc::Sender, task::JoinHandle};
@@ -15,20 +17,26 @@ mod notification;
 use database as db;
 mod configuration;
 mod error;
+mod event;
+mod history;
 mod ipc;
 mod line_handler;
 mod logging;
 mod report;
-
-use crate::error::ConfigurationError;
+mod stats;
+mod status;
+
+use crate::command::output::OutputFormat;
+use crate::command::{handler, util, CommandType};
+use crate::database::StorageGlue;
+use crate::error::DatabaseError;
+use crate::event::{
+    spawn_event_dispatcher, ArcSubscribers, Event, EventKind, EventSender, Subscriber,
+};
 use crate::ipc::{create_client_uds, create_server_uds, Bincodec, MessageRequest, MessageResponse};
 use crate::notification::archived_notification;
-use crate::notification::notify::{notify_break, notify_work};
+use crate::notification::notify::{notify_break, notify_start, notify_work};
 use crate::notification::Notification;
-use crate::{
-    command::{handler, util, CommandType},
-    ipc::{get_uds_address, UdsType},
-};
 use crate::{
     configuration::{get_configuration, Configuration},
     ipc::UdsMessage,
@@ -39,7 +47,7 @@ extern crate log;
 
 // key: notification id, value: spawned notification task
 pub type TaskMap = HashMap<u16, JoinHandle<()>>;
-pub type ArcGlue = Arc<Mutex<Glue<MemoryStorage>>>;
+pub type ArcGlue = Arc<Mutex<StorageGlue>>;
 pub type ArcTaskMap = Arc<Mutex<TaskMap>>;
 
 #[derive(Debug)]
@@ -47,6 +55,25 @@ pub struct UserInput {
     pub input: String,
     // pub oneshot_tx: oneshot::Sender<String>,
     pub source: InputSource,
+    pub output: OutputFormat,
+    // the address of the uds client which the output is sent back to
+    pub reply_to: Option<PathBuf>,
+}
+
+impl UserInput {
+    /// with_output sets the format of the output sent back to the uds client
+    pub fn with_output(mut self, output: OutputFormat) -> Self {
+        self.output = output;
+
+        self
+    }
+
+    /// with_reply_to sets the address of the uds client which sent the input
+    pub fn with_reply_to(mut self, reply_to: PathBuf) -> Self {
+        self.reply_to = Some(reply_to);
+
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -63,13 +90,32 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let command_type = detect_command_type().await?;
 
     match command_type {
-        CommandType::StartUp(config) => {
+        CommandType::StartUp(config, server_addr) => {
             info!("start pomodoro...");
             debug!("CommandType::StartUp");
 
-            let glue = initialize_db().await;
-            let mut id_manager: u16 = 1;
+            let glue = initialize_db(&config).await?;
+            // u16::MAX is left as it is, so that creating a notification fails instead of reusing it
+            let mut id_manager: u16 = db::read_last_id(glue.clone()).await?.saturating_add(1);
             let hash_map: Arc<Mutex<TaskMap>> = Arc::new(Mutex::new(HashMap::new()));
+            let (event_tx, event_rx) = mpsc::unbounded_channel::<Event>();
+            let subscribers: ArcSubscribers = Arc::new(Mutex::new(Vec::new()));
+
+            match notification::resume_notifications(
+                config.clone(),
+                hash_map.clone(),
+                glue.clone(),
+                event_tx.clone(),
+            )
+            .await
+            {
+                Ok((resumed, archived)) => info!(
+                    "{} notification(s) resumed, {} expired notification(s) archived",
+                    resumed, archived
+                ),
+                Err(e) => println!("There was an error resuming notifications: {}", e),
+            }
+
             let (user_input_tx, mut user_input_rx) = mpsc::channel::<UserInput>(64);
 
             let stdin_tx = user_input_tx.clone();
@@ -79,13 +125,20 @@ async fn main() -> Result<(), Box<dyn Error>> {
             // handle uds
             let uds_input_tx = user_input_tx.clone();
 
-            let server_uds_option = create_server_uds().await.unwrap();
+            let server_uds_option = create_server_uds(&server_addr).await.unwrap();
             let server_tx = match server_uds_option {
                 Some(uds) => {
                     let server_uds = Arc::new(uds);
                     let (server_rx, server_tx) = (server_uds.clone(), server_uds.clone());
-                    let _uds_input_handle =
-                        spawn_uds_input_handler(uds_input_tx, server_tx, server_rx);
+                    let _uds_input_handle = spawn_uds_input_handler(
+                        uds_input_tx,
+                        server_tx,
+                        server_rx,
+                        server_addr,
+                        subscribers.clone(),
+                    );
+                    let _event_handle =
+                        spawn_event_dispatcher(event_rx, server_uds.clone(), subscribers);
 
                     Some(server_uds)
                 }
@@ -100,15 +153,27 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 debug!("input: {:?}", input);
 
                 // handle input
-                match handler::user_input::handle(input, &mut id_manager, &hash_map, &glue, &config)
-                    .await
+                // the prompt is not printed again if nothing is printed for the uds client,
+                // e.g. the status requested by status bars every second
+                let mut should_prompt = true;
+                match handler::user_input::handle(
+                    &user_input,
+                    &mut id_manager,
+                    &hash_map,
+                    &glue,
+                    &config,
+                    &event_tx,
+                )
+                .await
                 {
                     Ok(mut output) => match user_input.source {
                         InputSource::StandardInput => {}
                         InputSource::UnixDomainSocket => {
-                            if let Some(ref server_tx) = server_tx {
-                                let client_addr = get_uds_address(UdsType::Client);
-                                ipc::send_to(
+                            should_prompt = output.has_printed();
+                            if let (Some(server_tx), Some(client_addr)) =
+                                (&server_tx, user_input.reply_to.clone())
+                            {
+                                let result = ipc::send_to(
                                     server_tx,
                                     client_addr,
                                     MessageResponse::new(output.take_body())
@@ -116,28 +181,33 @@ async fn main() -> Result<(), Box<dyn Error>> {
                                         .as_slice(),
                                 )
                                 .await;
+                                if let Err(e) = result {
+                                    debug!("failed to send the response: {:?}", e);
+                                }
                             }
                         }
                     },
                     Err(e) => {
-                        println!("There was an error analyzing the input: {}", e);
+                        let message = format!("There was an error analyzing the input: {}", e);
+                        println!("{}", message);
 
                         match user_input.source {
                             InputSource::StandardInput => {}
                             InputSource::UnixDomainSocket => {
-                                if let Some(ref server_tx) = server_tx {
-                                    let client_addr = get_uds_address(UdsType::Client);
-                                    ipc::send_to(
+                                if let (Some(server_tx), Some(client_addr)) =
+                                    (&server_tx, user_input.reply_to.clone())
+                                {
+                                    let result = ipc::send_to(
                                         server_tx,
                                         client_addr,
-                                        MessageResponse::new(vec![format!(
-                                            "There was an error analyzing the input: {}",
-                                            e
-                                        )])
-                                        .encode()?
-                                        .as_slice(),
+                                        MessageResponse::error(message, user_input.output)
+                                            .encode()?
+                                            .as_slice(),
                                     )
                                     .await;
+                                    if let Err(e) = result {
+                                        debug!("failed to send the response: {:?}", e);
+                                    }
                                 }
                             }
                         }
@@ -145,13 +215,19 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 }
 
                 debug!("input: {:?}", user_input);
-                util::print_start_up();
+                if should_prompt {
+                    util::print_start_up();
+                }
             }
         }
-        CommandType::UdsClient(matches) => {
+        CommandType::UdsClient(matches, server_addr) => {
             debug!("CommandType::UdsClient");
-            let socket = create_client_uds().await?;
-            handler::uds_client::handle(matches, socket).await?;
+            let (socket, client_addr) = create_client_uds(&server_addr).await?;
+            let result = handler::uds_client::handle(matches, socket).await;
+            if let Err(e) = ipc::remove_client_uds(&client_addr) {
+                debug!("failed to remove the client socket: {:?}", e);
+            }
+            result?;
         }
         CommandType::AutoComplete(sub_matches) => {
             if sub_matches.contains_id("shell") {
@@ -172,17 +248,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn detect_command_type() -> Result<CommandType, ConfigurationError> {
+async fn detect_command_type() -> Result<CommandType, Box<dyn Error>> {
     let matches = command::get_start_and_uds_client_command().get_matches();
     debug!("handle_uds_client_command, matches: {:?}", &matches);
 
     let command_type = match matches.subcommand().is_none() {
-        true => CommandType::StartUp(get_configuration(&matches)?),
+        true => CommandType::StartUp(
+            get_configuration(&matches)?,
+            ipc::get_server_address(matches.get_one::<String>("socket"))?,
+        ),
         false => {
             if let Some(val) = matches.subcommand_matches("completion") {
                 CommandType::AutoComplete(val.to_owned())
             } else {
-                CommandType::UdsClient(matches)
+                let server_addr = ipc::get_server_address(matches.get_one::<String>("socket"))?;
+                CommandType::UdsClient(matches, server_addr)
             }
         }
     };
@@ -190,11 +270,11 @@ async fn detect_command_type() -> Result<CommandType, ConfigurationError> {
     Ok(command_type)
 }
 
-async fn initialize_db() -> Arc<Mutex<Glue<MemoryStorage>>> {
-    let glue = Arc::new(Mutex::new(db::get_memory_glue()));
-    db::initialize(glue.clone()).await;
+async fn initialize_db(configuration: &Configuration) -> Result<ArcGlue, DatabaseError> {
+    let glue = Arc::new(Mutex::new(db::get_glue(configuration)?));
+    db::initialize(glue.clone()).await?;
 
-    glue
+    Ok(glue)
 }
 
 // TODO(young): refactor and move to proper place
@@ -202,24 +282,48 @@ pub fn spawn_notification(
     configuration: Arc<Configuration>,
     hash_map: Arc<Mutex<TaskMap>>,
     glue: ArcGlue,
+    event_tx: EventSender,
     notification: Notification,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
-        let (id, _, work_time, break_time, _, _, _) = notification.get_values();
+        let (id, _, work_time, break_time, _, work_expired_at, break_expired_at) =
+            notification.get_values();
         debug!("id: {}, task started", id);
 
-        let before_start_remaining = (notification.get_start_at() - Utc::now()).num_seconds();
-        let before = tokio::time::Duration::from_secs(before_start_remaining as u64);
-        debug!("before_start_remaining: {:?}", before_start_remaining);
+        let before = get_remaining_duration(notification.get_start_at());
+        debug!("before_start_remaining: {:?}", before);
         sleep(before).await;
 
-        if work_time > 0 {
-            let wt = tokio::time::Duration::from_secs(work_time as u64 * 60);
-            sleep(wt).await;
+        // the session started notification is only for the one which waited to start,
+        // e.g. scheduled or queued one. The event of the one starting right away
+        // is published when it is created
+        if work_time > 0 && before > Duration::ZERO && Utc::now() < work_expired_at {
+            debug!("id ({}), work time ({}) started", id, work_time);
+            event::publish(
+                &event_tx,
+                Event::new(EventKind::WorkStarted, &notification, Utc::now()),
+            );
+
+            // TODO(young): handle notify report err
+            let result = notify_start(&configuration, &notification).await;
+            if let Ok(report) = result {
+                info!("\n{}", report);
+                println!("Notification report generated");
+                util::write_output(&mut io::stdout());
+            }
+        }
+
+        // phases already expired (e.g. while pomodoro was not running) are skipped
+        if work_time > 0 && Utc::now() < work_expired_at {
+            sleep(get_remaining_duration(work_expired_at)).await;
             debug!("id ({}), work time ({}) done", id, work_time);
+            event::publish(
+                &event_tx,
+                Event::new(EventKind::WorkDone, &notification, Utc::now()),
+            );
 
             // TODO(young): handle notify report err
-            let result = notify_work(&configuration).await;
+            let result = notify_work(&configuration, &notification).await;
             if let Ok(report) = result {
                 info!("\n{}", report);
                 println!("Notification report generated");
@@ -227,13 +331,16 @@ pub fn spawn_notification(
             }
         }
 
-        if break_time > 0 {
-            let bt = tokio::time::Duration::from_secs(break_time as u64 * 60);
-            sleep(bt).await;
+        if break_time > 0 && Utc::now() < break_expired_at {
+            sleep(get_remaining_duration(break_expired_at)).await;
             debug!("id ({}), break time ({}) done", id, break_time);
+            event::publish(
+                &event_tx,
+                Event::new(EventKind::BreakDone, &notification, Utc::now()),
+            );
 
             // TODO(young): handle notify report err
-            let result = notify_break(&configuration).await;
+            let result = notify_break(&configuration, &notification).await;
             if let Ok(report) = result {
                 info!("\n{}", report);
                 println!("Notification report generated");
@@ -242,42 +349,90 @@ pub fn spawn_notification(
         }
 
         let result = notification::delete_notification(id, hash_map, glue.clone()).await;
-        if result.is_err() {
-            trace!("error occurred while deleting notification");
+        if let Err(e) = result {
+            trace!("error occurred while deleting notification: {}", e);
         }
 
         debug!("id: {}, notification work time done!", id);
     })
 }
 
+/// get_remaining_duration returns the duration from now to the given time.
+/// It returns zero duration if the given time is already passed.
+fn get_remaining_duration(until: DateTime<Utc>) -> Duration {
+    (until - Utc::now()).to_std().unwrap_or(Duration::ZERO)
+}
+
 fn spawn_uds_input_handler(
     uds_tx: Sender<UserInput>,
     server_tx: Arc<UnixDatagram>,
     server_rx: Arc<UnixDatagram>,
+    server_addr: PathBuf,
+    subscribers: ArcSubscribers,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         // TODO(young) handle result
         let rx = server_rx;
-        let mut buf = vec![0u8; 256];
+        let mut buf = vec![0u8; ipc::REQUEST_BUFFER_LENGTH];
         debug!("rx is initialized successfully");
         loop {
             debug!("inside unix domain socket task");
             // TODO(young) handle result
-            let (size, addr) = rx.recv_from(&mut buf).await.unwrap();
-            debug!("size: {:?}, addr: {:?}", size, addr);
+            let request = ipc::recv_request(&rx, &mut buf).await.unwrap();
+            debug!("request: {:?}", request);
 
-            if let Some(path) = addr.as_pathname() {
-                // ignore request from unnamed address
-                if path != get_uds_address(ipc::UdsType::Client).as_path() {
+            let path = match &request.path {
+                // ignore request from unnamed address, which can't receive the response
+                Some(path) if ipc::is_client_address(&server_addr, path) => path.clone(),
+                _ => {
                     debug!("addr is different");
                     continue;
                 }
-            }
+            };
 
-            let uds_message = UdsMessage::decode(&buf[..size]).unwrap();
+            if !request.is_same_user() {
+                debug!("request from another user is ignored, addr: {:?}", path);
+                continue;
+            }
+            let size = request.size;
+
+            let uds_message = match UdsMessage::decode(&buf[..size]) {
+                Ok(uds_message) => uds_message,
+                Err(e) => {
+                    debug!("failed to decode the request: {:?}", e);
+
+                    let response = MessageResponse::error(
+                        format!(
+                            "Error: failed to decode the request, which should be shorter than {} bytes",
+                            ipc::REQUEST_BUFFER_LENGTH
+                        ),
+                        OutputFormat::Text,
+                    );
+                    let result = ipc::send_to(&server_tx, path, &response.encode().unwrap()).await;
+                    if let Err(e) = result {
+                        debug!("failed to send the response: {:?}", e);
+                    }
+                    continue;
+                }
+            };
             match uds_message {
-                UdsMessage::Public(message) => {
-                    let user_input: UserInput = MessageRequest::into(message);
+                UdsMessage::Public(MessageRequest::Subscribe, output) => {
+                    // the confirmation goes first, or the chunks of an event would interleave with it
+                    let response = MessageResponse::new(vec![match output {
+                        OutputFormat::Text => String::from("Subscribed to the notification events"),
+                        OutputFormat::Json => json!({ "event": "subscribed" }).to_string(),
+                    }]);
+                    let result =
+                        ipc::send_to(&server_tx, path.clone(), &response.encode().unwrap()).await;
+                    match result {
+                        Ok(_) => event::subscribe(&subscribers, Subscriber::new(path, output)),
+                        Err(e) => debug!("failed to send the response: {:?}", e),
+                    }
+                }
+                UdsMessage::Public(message, output) => {
+                    let user_input = UserInput::from(message)
+                        .with_output(output)
+                        .with_reply_to(path);
                     debug!("user_input: {:?}", user_input);
 
                     uds_tx.send(user_input).await.unwrap();
@@ -286,12 +441,15 @@ fn spawn_uds_input_handler(
                     debug!("internal_message ok, {:?}", message);
                     match message {
                         ipc::internal::Message::Ping => {
-                            ipc::send_to(
+                            let result = ipc::send_to(
                                 &server_tx,
-                                addr.as_pathname().unwrap().to_path_buf(),
+                                path,
                                 ipc::internal::Message::Pong.encode().unwrap().as_slice(),
                             )
                             .await;
+                            if let Err(e) = result {
+                                debug!("failed to send pong: {:?}", e);
+                            }
                         }
                         ipc::internal::Message::Pong => {}
                     }
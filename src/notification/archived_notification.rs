@@ -1,21 +1,29 @@
 use chrono::{prelude::*, Duration};
+use chrono_tz::Tz;
 use std::borrow::Cow;
 use tabled::Tabled;
 
+use crate::command::util;
 use crate::notification::Notification;
 
 pub struct ArchivedNotification {
     id: u16,
     description: String,
-    work_time: u16,
-    break_time: u16,
+    work_time: u32,
+    break_time: u32,
     work_expired_at: DateTime<Utc>,
     break_expired_at: DateTime<Utc>,
+    cut_short: bool,
+    cycle: Option<(u16, u16)>,
+    // the timezone to show the timestamps in, which is not stored
+    timezone: Option<Tz>,
 }
 
 impl From<Notification> for ArchivedNotification {
     fn from(n: Notification) -> Self {
         let (id, desc, wt, bt, _, w_expired_at, b_expired_at) = n.get_values();
+        let cut_short = n.is_cut_short();
+        let cycle = n.get_cycle();
 
         ArchivedNotification {
             id,
@@ -24,53 +32,92 @@ impl From<Notification> for ArchivedNotification {
             break_time: bt,
             work_expired_at: w_expired_at,
             break_expired_at: b_expired_at,
+            cut_short,
+            cycle,
+            timezone: None,
         }
     }
 }
 
 impl ArchivedNotification {
-    pub fn get_start_at(&self) -> DateTime<Utc> {
-        let last_expired_at = self.work_expired_at.max(self.break_expired_at);
-        let duration = Duration::minutes((self.work_time + self.break_time) as i64);
+    /// with_timezone sets the timezone to show the timestamps in. None means the local timezone.
+    pub fn with_timezone(mut self, timezone: Option<Tz>) -> Self {
+        self.timezone = timezone;
+
+        self
+    }
+
+    pub fn get_id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn get_description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    pub fn get_work_time(&self) -> u32 {
+        self.work_time
+    }
 
-        last_expired_at - duration
+    pub fn get_break_time(&self) -> u32 {
+        self.break_time
+    }
+
+    pub fn get_work_expired_at(&self) -> DateTime<Utc> {
+        self.work_expired_at
+    }
+
+    pub fn get_break_expired_at(&self) -> DateTime<Utc> {
+        self.break_expired_at
+    }
+
+    pub fn is_cut_short(&self) -> bool {
+        self.cut_short
+    }
+
+    pub fn get_start_at(&self) -> DateTime<Utc> {
+        self.work_expired_at - Duration::seconds(self.work_time as i64)
     }
 }
 
 impl Tabled for ArchivedNotification {
-    const LENGTH: usize = 7;
+    const LENGTH: usize = 9;
 
     fn fields(&self) -> Vec<Cow<'_, str>> {
         let id = self.id.to_string();
 
-        let started_at = {
-            let local_time: DateTime<Local> = self.get_start_at().into();
-            local_time.format("%F %T %z").to_string()
-        };
+        let started_at = util::format_timestamp(self.get_start_at(), self.timezone);
 
         let description = self.description.to_string();
 
         let work_expired_at = if self.work_time > 0 {
-            let local_time: DateTime<Local> = self.work_expired_at.into();
-            local_time.format("%F %T %z").to_string()
+            util::format_timestamp(self.work_expired_at, self.timezone)
         } else {
             String::from("N/A")
         };
 
         let break_expired_at = if self.break_time > 0 {
-            let local_time: DateTime<Local> = self.break_expired_at.into();
-            local_time.format("%F %T %z").to_string()
+            util::format_timestamp(self.break_expired_at, self.timezone)
         } else {
             String::from("N/A")
         };
 
+        let cut_short = if self.cut_short { "yes" } else { "no" }.to_string();
+
+        let cycle = match self.cycle {
+            Some((index, total)) => format!("{}/{}", index, total),
+            None => String::from("N/A"),
+        };
+
         vec![
             id,
-            self.work_time.to_string(),
-            self.break_time.to_string(),
+            util::format_duration(self.work_time),
+            util::format_duration(self.break_time),
             started_at,
             work_expired_at,
             break_expired_at,
+            cut_short,
+            cycle,
             description,
         ]
         .into_iter()
@@ -86,6 +133,8 @@ impl Tabled for ArchivedNotification {
             "started_at",
             "expired_at (work)",
             "expired_at (break)",
+            "cut_short",
+            "cycle",
             "description",
         ]
         .into_iter()
@@ -105,7 +154,7 @@ mod tests {
     #[test]
     fn test_archived_notification_conversion() {
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
         let archived_notification = ArchivedNotification::from(notification);
 
         assert_eq!(
@@ -116,17 +165,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_archived_notification_cut_short() {
+        let now = Utc::now();
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.skip(now + chrono::Duration::minutes(10));
+        let archived_notification = ArchivedNotification::from(notification);
+
+        let fields = archived_notification.fields();
+        assert_eq!("10m", fields[1]);
+        assert_eq!("yes", fields[6]);
+    }
+
+    #[test]
+    fn test_archived_notification_cycle() {
+        let notification =
+            Notification::new(0, "sample", 25 * 60, 5 * 60, Utc::now()).with_cycle(3, 4);
+        let archived_notification = ArchivedNotification::from(notification);
+
+        let fields = archived_notification.fields();
+        assert_eq!("3/4", fields[7]);
+    }
+
     #[test]
     fn test_archived_notification_tabled_impl() {
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
         let archived_notification = ArchivedNotification::from(notification);
 
         let fields = archived_notification.fields();
-        assert_eq!(7, fields.len());
+        assert_eq!(9, fields.len());
+        assert_eq!("no", fields[6]);
+        assert_eq!("N/A", fields[7]);
 
         let headers = ArchivedNotification::headers();
-        assert_eq!(7, headers.len());
+        assert_eq!(9, headers.len());
         assert_eq!(
             vec![
                 "id".to_string(),
@@ -135,6 +208,8 @@ mod tests {
                 "started_at".to_string(),
                 "expired_at (work)".to_string(),
                 "expired_at (break)".to_string(),
+                "cut_short".to_string(),
+                "cycle".to_string(),
                 "description".to_string(),
             ],
             headers
@@ -1,60 +1,277 @@
 pub(crate) mod archived_notification;
+pub(crate) mod notifier;
 pub(crate) mod notify;
 
 pub use archived_notification::*;
 pub use notify::*;
 
 use chrono::{prelude::*, Duration};
+use chrono_tz::Tz;
 use clap::ArgMatches;
 use gluesql::core::data::Value;
 use gluesql::prelude::Row;
+use serde::Serialize;
 use std::borrow::Cow;
 use std::sync::Arc;
 use tabled::Tabled;
 
-use crate::command::util;
-use crate::configuration::Configuration;
+use crate::command::{util, DEFAULT_LONG_BREAK_INTERVAL, DEFAULT_LONG_BREAK_TIME};
+use crate::configuration::{Configuration, MissedNotificationPolicy};
 use crate::db;
-use crate::error::NotificationError;
-use crate::{ArcGlue, ArcTaskMap};
+use crate::error::{DatabaseError, NotificationError, ParseError};
+use crate::event::{self, Event, EventKind, EventSender};
+use crate::history::ImportedRecord;
+use crate::{spawn_notification, ArcGlue, ArcTaskMap};
 
 /// The notification schema used to store to database
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Notification {
     id: u16,
     description: String,
-    work_time: u16,
-    break_time: u16,
+    // work_time and break_time are in seconds
+    work_time: u32,
+    break_time: u32,
     created_at: DateTime<Utc>,
     work_expired_at: DateTime<Utc>,
     break_expired_at: DateTime<Utc>,
+    paused_at: Option<DateTime<Utc>>,
+    cut_short: bool,
+    cycle_index: u16,
+    cycle_total: u16,
+    // the id of the notification this one is queued behind, set only by queue and cycle
+    queued_after: Option<u16>,
+    // the timezone to show the timestamps in, which is not stored
+    timezone: Option<Tz>,
 }
 
 impl<'a> Notification {
-    pub fn new(id: u16, work_time: u16, break_time: u16, created_at: DateTime<Utc>) -> Self {
-        let work_expired_at = created_at + Duration::minutes(work_time as i64);
-        let break_expired_at = work_expired_at + Duration::minutes(break_time as i64);
+    pub fn new(
+        id: u16,
+        description: &str,
+        work_time: u32,
+        break_time: u32,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        let work_expired_at = created_at + Duration::seconds(work_time as i64);
+        let break_expired_at = work_expired_at + Duration::seconds(break_time as i64);
 
         Notification {
             id,
-            description: String::from("sample"),
+            description: description.to_string(),
             work_time,
             break_time,
             created_at,
             work_expired_at,
             break_expired_at,
+            paused_at: None,
+            cut_short: false,
+            cycle_index: 0,
+            cycle_total: 0,
+            queued_after: None,
+            timezone: None,
         }
     }
 
+    /// with_cycle marks the notification as the `cycle_index`th pomodoro of a cycle.
+    pub fn with_cycle(mut self, cycle_index: u16, cycle_total: u16) -> Self {
+        self.cycle_index = cycle_index;
+        self.cycle_total = cycle_total;
+
+        self
+    }
+
+    /// with_queued_after queues the notification behind the given one, so that pausing
+    /// or postponing that one also moves this one.
+    pub fn with_queued_after(mut self, queued_after: Option<u16>) -> Self {
+        self.queued_after = queued_after;
+
+        self
+    }
+
+    /// with_timezone sets the timezone to show the timestamps in. None means the local timezone.
+    pub fn with_timezone(mut self, timezone: Option<Tz>) -> Self {
+        self.timezone = timezone;
+
+        self
+    }
+
     pub fn get_id(&self) -> u16 {
         self.id
     }
 
+    pub fn get_description(&self) -> &str {
+        self.description.as_str()
+    }
+
     pub fn get_start_at(&self) -> DateTime<Utc> {
-        let last_expired_at = self.work_expired_at.max(self.break_expired_at);
-        let duration = Duration::minutes((self.work_time + self.break_time) as i64);
+        // break_expired_at is shifted alone when paused during the break,
+        // so the start is derived from the work phase only
+        self.work_expired_at - Duration::seconds(self.work_time as i64)
+    }
+
+    pub fn get_last_expired_at(&self) -> DateTime<Utc> {
+        self.work_expired_at.max(self.break_expired_at)
+    }
+
+    pub fn get_paused_at(&self) -> Option<DateTime<Utc>> {
+        self.paused_at
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn is_cut_short(&self) -> bool {
+        self.cut_short
+    }
+
+    /// get_cycle returns the index and the total count of the cycle,
+    /// or None if the notification is not a part of a cycle.
+    pub fn get_cycle(&self) -> Option<(u16, u16)> {
+        if self.cycle_total > 0 {
+            Some((self.cycle_index, self.cycle_total))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_queued_after(&self) -> Option<u16> {
+        self.queued_after
+    }
+
+    /// get_state returns which phase the notification is in at the given time
+    pub fn get_state(&self, current_time: DateTime<Utc>) -> NotificationState {
+        if self.is_paused() {
+            NotificationState::Paused
+        } else if current_time < self.get_start_at() {
+            NotificationState::Pending
+        } else if current_time < self.work_expired_at {
+            NotificationState::Work
+        } else if current_time < self.break_expired_at {
+            NotificationState::Break
+        } else {
+            NotificationState::Done
+        }
+    }
+
+    pub fn pause(&mut self, paused_at: DateTime<Utc>) {
+        self.paused_at = Some(paused_at);
+    }
+
+    /// resume postpones the remaining phases by the time spent while paused.
+    pub fn resume(&mut self, resumed_at: DateTime<Utc>) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.postpone(paused_at, resumed_at - paused_at);
+        }
+    }
+
+    /// postpone shifts every timestamp which is later than `since` by `duration`.
+    /// Phases already finished at `since` are left untouched.
+    pub fn postpone(&mut self, since: DateTime<Utc>, duration: Duration) {
+        for timestamp in [
+            &mut self.created_at,
+            &mut self.work_expired_at,
+            &mut self.break_expired_at,
+        ] {
+            if *timestamp > since {
+                *timestamp += duration;
+            }
+        }
+    }
+
+    /// extend adds the seconds to the phase running at the given time.
+    /// A notification not started yet is extended from its first phase.
+    /// The notification is left as it is if the time of the phase overflows.
+    pub fn extend(
+        &mut self,
+        current_time: DateTime<Utc>,
+        seconds: u32,
+    ) -> Result<(), NotificationError> {
+        let extended = if self.work_time > 0 && current_time < self.work_expired_at {
+            self.add_work_time(seconds)
+        } else {
+            self.add_break_time(seconds)
+        };
+
+        extended.ok_or_else(|| {
+            NotificationError::ExtendFail(format!(
+                "extending id ({}) failed. The time of the phase is too long",
+                self.id
+            ))
+        })
+    }
+
+    /// snooze postpones the end of the break, so that the break notification fires later.
+    pub fn snooze(&mut self, seconds: u32) -> Result<(), NotificationError> {
+        self.add_break_time(seconds).ok_or_else(|| {
+            NotificationError::SnoozeFail(format!(
+                "snoozing id ({}) failed. The break time is too long",
+                self.id
+            ))
+        })
+    }
+
+    fn add_work_time(&mut self, seconds: u32) -> Option<()> {
+        let duration = Duration::seconds(seconds as i64);
+        let work_time = self.work_time.checked_add(seconds)?;
+        let work_expired_at = self.work_expired_at.checked_add_signed(duration)?;
+        let break_expired_at = self.break_expired_at.checked_add_signed(duration)?;
+
+        self.work_time = work_time;
+        self.work_expired_at = work_expired_at;
+        self.break_expired_at = break_expired_at;
+
+        Some(())
+    }
+
+    fn add_break_time(&mut self, seconds: u32) -> Option<()> {
+        let break_time = self.break_time.checked_add(seconds)?;
+        let break_expired_at = self
+            .break_expired_at
+            .checked_add_signed(Duration::seconds(seconds as i64))?;
+
+        self.break_time = break_time;
+        self.break_expired_at = break_expired_at;
 
-        last_expired_at - duration
+        Some(())
+    }
+
+    /// skip ends the phase running at the given time. The time of the phase is replaced
+    /// with the seconds actually spent, and the remaining break starts right away.
+    pub fn skip(&mut self, current_time: DateTime<Utc>) {
+        if self.work_time > 0 && current_time < self.work_expired_at {
+            self.work_time = (current_time - self.get_start_at()).num_seconds() as u32;
+            self.work_expired_at = current_time;
+            self.break_expired_at = current_time + Duration::seconds(self.break_time as i64);
+        } else if current_time < self.break_expired_at {
+            self.break_time = (current_time - self.work_expired_at).num_seconds() as u32;
+            self.break_expired_at = current_time;
+        }
+
+        self.cut_short = true;
+    }
+
+    /// cut_off drops what is not spent by the given time, e.g. when the notification is deleted,
+    /// so that the history keeps only the time actually spent. A paused notification is cut
+    /// at the moment it was paused, and a pending one is left without any phase.
+    pub fn cut_off(&mut self, current_time: DateTime<Utc>) {
+        let current_time = self.paused_at.take().unwrap_or(current_time);
+        if current_time >= self.get_last_expired_at() {
+            return;
+        }
+
+        let start_at = self.get_start_at();
+        let cut_at = current_time.max(start_at);
+        if cut_at < self.work_expired_at {
+            self.work_time = (cut_at - start_at).num_seconds() as u32;
+            self.work_expired_at = cut_at;
+            self.break_time = 0;
+        } else {
+            self.break_time = (cut_at - self.work_expired_at).num_seconds() as u32;
+        }
+        self.break_expired_at = cut_at;
+
+        self.cut_short = true;
     }
 
     pub fn get_values(
@@ -62,8 +279,8 @@ impl<'a> Notification {
     ) -> (
         u16,
         &'a str,
-        u16,
-        u16,
+        u32,
+        u32,
         DateTime<Utc>,
         DateTime<Utc>,
         DateTime<Utc>,
@@ -80,10 +297,13 @@ impl<'a> Notification {
     }
 
     pub fn get_work_percentage(&self, current_time: DateTime<Utc>) -> String {
+        self.get_work_percentage_value(current_time).to_string()
+    }
+
+    pub(crate) fn get_work_percentage_value(&self, current_time: DateTime<Utc>) -> i64 {
         // Check if the timer has started and contains some work_time
         if self.work_expired_at > current_time && self.get_start_at() < current_time {
-            // do the calculation in seconds for better % accuracy
-            let work_time_seconds: i64 = self.work_time as i64 * 60;
+            let work_time_seconds: i64 = self.work_time as i64;
 
             let completed_time =
                 work_time_seconds - (self.work_expired_at - current_time).num_seconds();
@@ -95,60 +315,104 @@ impl<'a> Notification {
             // no work_time = 100% work done
             100
         }
-        .to_string()
     }
 
-    pub fn convert_to_notification(row: Row) -> Self {
-        let id = match row.get_value_by_index(0).unwrap() {
-            Value::I64(id) => *id as u16,
-            _ => {
-                panic!("notification id type mismatch");
+    /// to_record returns the machine-readable form of the notification at the given time.
+    pub fn to_record(&self, current_time: DateTime<Utc>) -> NotificationRecord {
+        // the remaining time is frozen while paused
+        let utc = self.paused_at.unwrap_or(current_time);
+        let remaining = |time: u32, until: DateTime<Utc>| {
+            if time > 0 {
+                u32::try_from((until - utc).num_seconds().max(0)).unwrap_or(u32::MAX)
+            } else {
+                0
             }
         };
 
-        let description = match row.get_value_by_index(1).unwrap() {
-            Value::Str(s) => s.to_owned(),
-            _ => {
-                panic!("notification description type mismatch");
-            }
+        NotificationRecord {
+            id: self.id,
+            description: self.description.clone(),
+            state: self.get_state(utc),
+            cycle: self
+                .get_cycle()
+                .map(|(index, total)| CycleRecord { index, total }),
+            work_time: self.work_time,
+            break_time: self.break_time,
+            work_remaining: remaining(self.work_time, self.work_expired_at),
+            break_remaining: remaining(self.break_time, self.break_expired_at),
+            percentage: self.get_work_percentage_value(utc),
+            started_at: util::to_rfc3339(self.get_start_at()),
+            work_expired_at: util::to_rfc3339(self.work_expired_at),
+            break_expired_at: util::to_rfc3339(self.break_expired_at),
+            paused_at: self.paused_at.map(util::to_rfc3339),
+            cut_short: self.cut_short,
+        }
+    }
+
+    pub fn convert_to_notification(row: Row) -> Result<Self, DatabaseError> {
+        let id = match row.get_value_by_index(0) {
+            Some(Value::I64(id)) => *id as u16,
+            _ => return Err(invalid_row("notification id type mismatch")),
         };
 
-        let work_time = match row.get_value_by_index(2).unwrap() {
-            Value::I64(t) => *t as u16,
-            _ => {
-                panic!("notification work_time type mismatch")
-            }
+        let description = match row.get_value_by_index(1) {
+            Some(Value::Str(s)) => s.to_owned(),
+            _ => return Err(invalid_row("notification description type mismatch")),
         };
 
-        let break_time = match row.get_value_by_index(3).unwrap() {
-            Value::I64(t) => *t as u16,
-            _ => {
-                panic!("notification break_time type mismatch")
-            }
+        let work_time = match row.get_value_by_index(2) {
+            Some(Value::I64(t)) => *t as u32,
+            _ => return Err(invalid_row("notification work_time type mismatch")),
         };
 
-        let created_at = match row.get_value_by_index(4).unwrap() {
-            Value::Timestamp(t) => Utc.from_local_datetime(t).unwrap(),
-            _ => {
-                panic!("notification created_at type mismatch");
-            }
+        let break_time = match row.get_value_by_index(3) {
+            Some(Value::I64(t)) => *t as u32,
+            _ => return Err(invalid_row("notification break_time type mismatch")),
         };
 
-        let work_expired_at = match row.get_value_by_index(5).unwrap() {
-            Value::Timestamp(t) => Utc.from_local_datetime(t).unwrap(),
-            _ => {
-                panic!("notification work_expired_at type mismatch");
-            }
+        let created_at = match row.get_value_by_index(4) {
+            Some(Value::Timestamp(t)) => Utc.from_utc_datetime(t),
+            _ => return Err(invalid_row("notification created_at type mismatch")),
         };
 
-        let break_expired_at = match row.get_value_by_index(6).unwrap() {
-            Value::Timestamp(t) => Utc.from_local_datetime(t).unwrap(),
-            _ => {
-                panic!("notification break_expired_at type mismatch");
-            }
+        let work_expired_at = match row.get_value_by_index(5) {
+            Some(Value::Timestamp(t)) => Utc.from_utc_datetime(t),
+            _ => return Err(invalid_row("notification work_expired_at type mismatch")),
         };
 
-        Notification {
+        let break_expired_at = match row.get_value_by_index(6) {
+            Some(Value::Timestamp(t)) => Utc.from_utc_datetime(t),
+            _ => return Err(invalid_row("notification break_expired_at type mismatch")),
+        };
+
+        let paused_at = match row.get_value_by_index(7) {
+            Some(Value::Timestamp(t)) => Some(Utc.from_utc_datetime(t)),
+            Some(Value::Null) => None,
+            _ => return Err(invalid_row("notification paused_at type mismatch")),
+        };
+
+        let cut_short = match row.get_value_by_index(8) {
+            Some(Value::Bool(b)) => *b,
+            _ => return Err(invalid_row("notification cut_short type mismatch")),
+        };
+
+        let cycle_index = match row.get_value_by_index(9) {
+            Some(Value::I64(i)) => *i as u16,
+            _ => return Err(invalid_row("notification cycle_index type mismatch")),
+        };
+
+        let cycle_total = match row.get_value_by_index(10) {
+            Some(Value::I64(t)) => *t as u16,
+            _ => return Err(invalid_row("notification cycle_total type mismatch")),
+        };
+
+        let queued_after = match row.get_value_by_index(11) {
+            Some(Value::I64(id)) => Some(*id as u16),
+            Some(Value::Null) => None,
+            _ => return Err(invalid_row("notification queued_after type mismatch")),
+        };
+
+        Ok(Notification {
             id,
             description,
             work_time,
@@ -156,18 +420,94 @@ impl<'a> Notification {
             created_at,
             work_expired_at,
             break_expired_at,
+            paused_at,
+            cut_short,
+            cycle_index,
+            cycle_total,
+            queued_after,
+            timezone: None,
+        })
+    }
+}
+
+fn invalid_row(message: &str) -> DatabaseError {
+    DatabaseError::InvalidRow(message.to_string())
+}
+
+/// The phase of the notification at some time
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationState {
+    Pending,
+    Work,
+    Break,
+    Paused,
+    Done,
+}
+
+impl NotificationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationState::Pending => "pending",
+            NotificationState::Work => "work",
+            NotificationState::Break => "break",
+            NotificationState::Paused => "paused",
+            NotificationState::Done => "done",
         }
     }
 }
 
+/// The notification printed with `--output json`. Times are in seconds and timestamps are in RFC 3339.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct NotificationRecord {
+    id: u16,
+    description: String,
+    state: NotificationState,
+    cycle: Option<CycleRecord>,
+    work_time: u32,
+    break_time: u32,
+    work_remaining: u32,
+    break_remaining: u32,
+    percentage: i64,
+    started_at: String,
+    work_expired_at: String,
+    break_expired_at: String,
+    paused_at: Option<String>,
+    cut_short: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct CycleRecord {
+    index: u16,
+    total: u16,
+}
+
 impl Tabled for Notification {
-    const LENGTH: usize = 8;
+    const LENGTH: usize = 11;
 
     fn fields(&self) -> Vec<Cow<'_, str>> {
-        let utc = Utc::now();
+        // the remaining time is frozen while paused
+        let utc = self.paused_at.unwrap_or_else(Utc::now);
 
         let id = self.id.to_string();
 
+        let state = self.get_state(utc).as_str().to_string();
+
+        let cycle = match self.get_cycle() {
+            Some((index, total)) => format!("{}/{}", index, total),
+            None => String::from("N/A"),
+        };
+
+        let starts_in = {
+            let sec = (self.get_start_at() - utc).num_seconds();
+
+            if sec > 0 {
+                util::format_duration(u32::try_from(sec).unwrap_or(u32::MAX))
+            } else {
+                String::from("N/A")
+            }
+        };
+
         let work_remaining = if self.work_time > 0 {
             let sec = (self.work_expired_at - utc).num_seconds();
 
@@ -175,7 +515,7 @@ impl Tabled for Notification {
                 let work_min = sec / 60;
                 let work_sec = sec - work_min * 60;
 
-                format!("{}:{}", work_min, work_sec)
+                format!("{:02}:{:02}", work_min, work_sec)
             } else {
                 String::from("00:00")
             }
@@ -190,7 +530,7 @@ impl Tabled for Notification {
                 let break_min = sec / 60;
                 let break_sec = sec - break_min * 60;
 
-                format!("{}:{}", break_min, break_sec)
+                format!("{:02}:{:02}", break_min, break_sec)
             } else {
                 String::from("00:00")
             }
@@ -198,23 +538,18 @@ impl Tabled for Notification {
             String::from("N/A")
         };
 
-        let start_at = {
-            let local_time: DateTime<Local> = self.get_start_at().into();
-            local_time.format("%F %T %z").to_string()
-        };
+        let start_at = util::format_timestamp(self.get_start_at(), self.timezone);
 
         let description = self.description.to_string();
 
         let work_expired_at = if self.work_time > 0 {
-            let local_time: DateTime<Local> = self.work_expired_at.into();
-            local_time.format("%F %T %z").to_string()
+            util::format_timestamp(self.work_expired_at, self.timezone)
         } else {
             String::from("N/A")
         };
 
         let break_expired_at = if self.break_time > 0 {
-            let local_time: DateTime<Local> = self.break_expired_at.into();
-            local_time.format("%F %T %z").to_string()
+            util::format_timestamp(self.break_expired_at, self.timezone)
         } else {
             String::from("N/A")
         };
@@ -223,6 +558,9 @@ impl Tabled for Notification {
 
         vec![
             id,
+            state,
+            cycle,
+            starts_in,
             work_remaining,
             break_remaining,
             start_at,
@@ -239,6 +577,9 @@ impl Tabled for Notification {
     fn headers() -> Vec<Cow<'static, str>> {
         vec![
             "id",
+            "state",
+            "cycle",
+            "starts_in",
             "work_remaining (min)",
             "break_remaining (min)",
             "start_at",
@@ -273,72 +614,771 @@ pub fn get_new_notification(
         return Err(NotificationError::EmptyTimeValues);
     }
 
-    let id = get_new_id(id_manager);
+    let description = matches
+        .get_one::<String>("message")
+        .map(|s| s.as_str())
+        .unwrap_or_default();
+    debug!("description: {}", description);
+
+    let id = get_new_id(id_manager)?;
+
+    Ok(Notification::new(
+        id,
+        description,
+        work_time,
+        break_time,
+        created_at,
+    ))
+}
+
+/// get_new_cycle_notifications returns the pomodoros of a cycle, each queued after the previous one.
+/// Every `long_break_interval`th pomodoro takes the long break instead of the short one.
+/// The first one is queued after `queued_after`, if any.
+pub fn get_new_cycle_notifications(
+    matches: &ArgMatches,
+    id_manager: &mut u16,
+    created_at: DateTime<Utc>,
+    queued_after: Option<u16>,
+    configuration: Arc<Configuration>,
+) -> Result<Vec<Notification>, NotificationError> {
+    let (work_time, break_time) = util::parse_work_and_break_time(matches, Some(&configuration))
+        .map_err(NotificationError::NewNotification)?;
+
+    // should never panic on unwrap as parse_work_and_break_time already handles it
+    let work_time = work_time.unwrap();
+    let break_time = break_time.unwrap();
+
+    let long_break_time = util::parse_optional_duration_arg(matches, "long_break")
+        .map_err(NotificationError::NewNotification)?
+        .unwrap_or_else(|| {
+            u32::from(
+                configuration
+                    .get_long_break_time()
+                    .unwrap_or(DEFAULT_LONG_BREAK_TIME),
+            ) * 60
+        });
+    let long_break_interval = configuration
+        .get_long_break_interval()
+        .unwrap_or(DEFAULT_LONG_BREAK_INTERVAL)
+        .max(1);
+    let count = util::parse_optional_arg::<u16>(matches, "count")
+        .map_err(NotificationError::NewNotification)?
+        .unwrap_or(long_break_interval);
+
+    debug!(
+        "work_time: {}, break_time: {}, long_break_time: {}, count: {}",
+        work_time, break_time, long_break_time, count
+    );
+
+    if work_time == 0 {
+        return Err(NotificationError::EmptyTimeValues);
+    }
 
-    Ok(Notification::new(id, work_time, break_time, created_at))
+    if count == 0 {
+        return Err(NotificationError::NewNotification(ParseError::new(
+            String::from("cycle count should be greater than 0"),
+        )));
+    }
+
+    let description = matches
+        .get_one::<String>("message")
+        .map(|s| s.as_str())
+        .unwrap_or_default();
+
+    let mut start_at = created_at;
+    let mut queued_after = queued_after;
+    (1..=count)
+        .map(|index| {
+            let break_time = if index % long_break_interval == 0 {
+                long_break_time
+            } else {
+                break_time
+            };
+
+            let notification = Notification::new(
+                get_new_id(id_manager)?,
+                description,
+                work_time,
+                break_time,
+                start_at,
+            )
+            .with_cycle(index, count)
+            .with_queued_after(queued_after);
+            start_at = notification.get_last_expired_at();
+            queued_after = Some(notification.get_id());
+
+            Ok(notification)
+        })
+        .collect()
 }
 
-fn get_new_id(id_manager: &mut u16) -> u16 {
+/// get_imported_notifications returns the notifications of the imported records with new ids,
+/// so that they never collide with the existing ones.
+pub fn get_imported_notifications(
+    records: &[ImportedRecord],
+    id_manager: &mut u16,
+) -> Result<Vec<Notification>, NotificationError> {
+    // the import is rejected as a whole rather than stopped in the middle
+    if usize::from(u16::MAX - *id_manager) < records.len() {
+        return Err(NotificationError::IdExhausted);
+    }
+
+    records
+        .iter()
+        .map(|r| {
+            let mut notification = Notification::new(
+                get_new_id(id_manager)?,
+                r.get_description(),
+                r.get_work_time(),
+                r.get_break_time(),
+                r.get_started_at(),
+            );
+            notification.cut_short = r.is_cut_short();
+
+            Ok(notification)
+        })
+        .collect()
+}
+
+/// get_new_id returns the next id. u16::MAX is never given,
+/// so that running out of ids is detected instead of wrapping around.
+fn get_new_id(id_manager: &mut u16) -> Result<u16, NotificationError> {
     let id = *id_manager;
-    *id_manager += 1;
+    *id_manager = id.checked_add(1).ok_or(NotificationError::IdExhausted)?;
 
-    id
+    Ok(id)
 }
 
+/// delete_notification archives the notification with the time spent so far and returns it
 pub async fn delete_notification(
     id: u16,
     notification_task_map: ArcTaskMap,
     glue: ArcGlue,
-) -> Result<(), NotificationError> {
-    let notification = db::read_notification(glue.clone(), id).await;
-    if notification.is_none() {
-        return Err(NotificationError::DeletionFail(format!(
-            "deleting id ({}) failed. Corresponding notification does not exist",
+) -> Result<Notification, NotificationError> {
+    let mut notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::DeletionFail(format!(
+                "deleting id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    // paused notifications and the ones queued behind them have no task
+    if let Some(handle) = notification_task_map.lock().unwrap().remove(&id) {
+        handle.abort();
+    }
+
+    notification.cut_off(Utc::now());
+    db::update_notification(glue.clone(), &notification)
+        .await
+        .map_err(NotificationError::Database)?;
+    db::delete_and_archive_notification(glue, id)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    Ok(notification)
+}
+
+/// pause_notification freezes the remaining time of the notification and returns it.
+/// Tasks of the notification and of the notifications queued behind it are aborted
+/// so that nothing fires until the notification is resumed.
+pub async fn pause_notification(
+    id: u16,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+) -> Result<Notification, NotificationError> {
+    let now = Utc::now();
+
+    let mut notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::PauseFail(format!(
+                "pausing id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    if notification.is_paused() {
+        return Err(NotificationError::PauseFail(format!(
+            "pausing id ({}) failed. Notification is already paused",
             id
         )));
     }
 
+    if notification.get_last_expired_at() <= now {
+        return Err(NotificationError::PauseFail(format!(
+            "pausing id ({}) failed. Notification is already finished",
+            id
+        )));
+    }
+
+    let queued_notifications = list_queued_notifications(glue.clone(), &notification).await?;
+
     {
         let mut hash_map = notification_task_map.lock().unwrap();
 
-        hash_map
-            .get(&id)
-            .ok_or(format!("failed to corresponding task (id: {})", &id))
-            .map_err(NotificationError::DeletionFail)?
-            .abort();
+        for id in std::iter::once(id).chain(queued_notifications.iter().map(|n| n.get_id())) {
+            if let Some(handle) = hash_map.remove(&id) {
+                handle.abort();
+            }
+        }
+    }
+
+    notification.pause(now);
+    db::update_notification(glue, &notification)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    Ok(notification)
+}
+
+/// resume_notification resumes the paused notification. The notification and
+/// the notifications queued behind it are postponed by the time spent while paused.
+pub async fn resume_notification(
+    id: u16,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+) -> Result<(), NotificationError> {
+    let now = Utc::now();
+
+    let mut notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::ResumeFail(format!(
+                "resuming id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    let paused_at = notification.get_paused_at().ok_or_else(|| {
+        NotificationError::ResumeFail(format!(
+            "resuming id ({}) failed. Notification is not paused",
+            id
+        ))
+    })?;
+
+    // queued notifications must be found before the paused one is postponed
+    let mut queued_notifications = list_queued_notifications(glue.clone(), &notification).await?;
 
-        hash_map
-            .remove(&id)
-            .ok_or(format!("failed to remove id ({})", id))
-            .map_err(NotificationError::DeletionFail)?;
+    notification.resume(now);
+    for queued_notification in queued_notifications.iter_mut() {
+        queued_notification.postpone(paused_at, now - paused_at);
     }
 
-    db::delete_and_archive_notification(glue, id).await;
+    update_and_respawn_notifications(
+        std::iter::once(notification).chain(queued_notifications),
+        configuration,
+        notification_task_map,
+        glue,
+        event_tx,
+    )
+    .await
+}
+
+/// extend_notification adds seconds to the current phase of the notification.
+/// The notifications queued behind it are postponed by the same seconds.
+pub async fn extend_notification(
+    id: u16,
+    seconds: u32,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+) -> Result<(), NotificationError> {
+    if seconds == 0 {
+        return Err(NotificationError::EmptyTimeValues);
+    }
+
+    let notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::ExtendFail(format!(
+                "extending id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    // a paused notification is extended from the moment it was paused
+    let current_time = notification.get_paused_at().unwrap_or_else(Utc::now);
+    if notification.get_last_expired_at() <= current_time {
+        return Err(NotificationError::ExtendFail(format!(
+            "extending id ({}) failed. Notification is already finished",
+            id
+        )));
+    }
+
+    postpone_with_queued_notifications(
+        notification,
+        configuration,
+        notification_task_map,
+        glue,
+        event_tx,
+        |n| n.extend(current_time, seconds),
+        seconds,
+    )
+    .await
+}
+
+/// snooze_notification fires the break notification again after the given seconds.
+/// If the notification is still running, its break is postponed like `extend_notification`.
+/// Otherwise a break only notification with the same description is created from
+/// the archived one, and its id is returned.
+pub async fn snooze_notification(
+    id: u16,
+    seconds: u32,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+    id_manager: &mut u16,
+) -> Result<u16, NotificationError> {
+    if seconds == 0 {
+        return Err(NotificationError::EmptyTimeValues);
+    }
+
+    let notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    if let Some(notification) = notification {
+        postpone_with_queued_notifications(
+            notification,
+            configuration,
+            notification_task_map,
+            glue,
+            event_tx,
+            |n| n.snooze(seconds),
+            seconds,
+        )
+        .await?;
+
+        return Ok(id);
+    }
+
+    let archived_notification = db::read_archived_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::SnoozeFail(format!(
+                "snoozing id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    let notification = Notification::new(
+        get_new_id(id_manager)?,
+        archived_notification.get_description(),
+        0,
+        seconds,
+        Utc::now(),
+    );
+    let new_id = notification.get_id();
+    db::create_notification(glue.clone(), &notification)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    respawn_notification(
+        configuration,
+        notification_task_map,
+        glue,
+        event_tx,
+        notification,
+    );
+
+    Ok(new_id)
+}
+
+/// postpone_with_queued_notifications applies `postpone` to the notification,
+/// then shifts the notifications queued behind it by the given seconds and reschedules all of them.
+async fn postpone_with_queued_notifications<F>(
+    mut notification: Notification,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+    postpone: F,
+    seconds: u32,
+) -> Result<(), NotificationError>
+where
+    F: FnOnce(&mut Notification) -> Result<(), NotificationError>,
+{
+    let now = Utc::now();
+
+    let mut queued_notifications = list_queued_notifications(glue.clone(), &notification).await?;
+
+    postpone(&mut notification)?;
+    for queued_notification in queued_notifications.iter_mut() {
+        queued_notification.postpone(now, Duration::seconds(seconds as i64));
+    }
+
+    update_and_respawn_notifications(
+        std::iter::once(notification).chain(queued_notifications),
+        configuration,
+        notification_task_map,
+        glue,
+        event_tx,
+    )
+    .await
+}
+
+/// skip_notification ends the current phase of the notification immediately.
+/// The matching notification is fired unless `silent` is set, and the notification
+/// is archived when no phase is left. The notifications queued behind it are brought forward.
+pub async fn skip_notification(
+    id: u16,
+    silent: bool,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+) -> Result<(), NotificationError> {
+    let now = Utc::now();
+
+    let mut notification = db::read_notification(glue.clone(), id)
+        .await
+        .map_err(NotificationError::Database)?
+        .ok_or_else(|| {
+            NotificationError::SkipFail(format!(
+                "skipping id ({}) failed. Corresponding notification does not exist",
+                id
+            ))
+        })?;
+
+    let state = notification.get_state(now);
+    if state != NotificationState::Work && state != NotificationState::Break {
+        return Err(NotificationError::SkipFail(format!(
+            "skipping id ({}) failed. Notification is {}",
+            id,
+            state.as_str()
+        )));
+    }
+
+    let mut queued_notifications = list_queued_notifications(glue.clone(), &notification).await?;
+    let last_expired_at = notification.get_last_expired_at();
+
+    notification.skip(now);
+    let kind = if state == NotificationState::Work {
+        EventKind::WorkDone
+    } else {
+        EventKind::BreakDone
+    };
+    event::publish(&event_tx, Event::new(kind, &notification, now));
+    let brought_forward = notification.get_last_expired_at() - last_expired_at;
+    for queued_notification in queued_notifications.iter_mut() {
+        queued_notification.postpone(now, brought_forward);
+    }
+
+    if let Some(handle) = notification_task_map.lock().unwrap().remove(&id) {
+        handle.abort();
+    }
+
+    // sent from a task as spawn_notification does, so that a slow channel does not hold up
+    // the other requests
+    if !silent {
+        let configuration = configuration.clone();
+        let notification = notification.clone();
+        tokio::spawn(async move {
+            let result = if state == NotificationState::Work {
+                notify_work(&configuration, &notification).await
+            } else {
+                notify_break(&configuration, &notification).await
+            };
+            if let Ok(report) = result {
+                info!("\n{}", report);
+            }
+        });
+    }
+
+    let notification = if notification.get_last_expired_at() <= now {
+        db::update_notification(glue.clone(), &notification)
+            .await
+            .map_err(NotificationError::Database)?;
+        db::delete_and_archive_notification(glue.clone(), id)
+            .await
+            .map_err(NotificationError::Database)?;
+        None
+    } else {
+        Some(notification)
+    };
+
+    update_and_respawn_notifications(
+        notification.into_iter().chain(queued_notifications),
+        configuration,
+        notification_task_map,
+        glue,
+        event_tx,
+    )
+    .await
+}
+
+/// list_queued_notifications returns the notifications queued behind the given one,
+/// directly or through other queued notifications.
+async fn list_queued_notifications(
+    glue: ArcGlue,
+    notification: &Notification,
+) -> Result<Vec<Notification>, NotificationError> {
+    let notifications = db::list_notification(glue)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    Ok(get_queued_notifications(
+        notification.get_id(),
+        notifications,
+    ))
+}
+
+/// get_queued_notifications follows the queue from the notification of the given id.
+/// A paused notification is left out together with the ones queued behind it,
+/// since they are moved when that one is resumed.
+fn get_queued_notifications(id: u16, mut notifications: Vec<Notification>) -> Vec<Notification> {
+    let mut queued_notifications = Vec::new();
+    let mut ids = vec![id];
+
+    while let Some(id) = ids.pop() {
+        let (queued, rest): (Vec<_>, Vec<_>) = notifications
+            .into_iter()
+            .partition(|n| n.get_queued_after() == Some(id) && !n.is_paused());
+        notifications = rest;
+
+        ids.extend(queued.iter().map(|n| n.get_id()));
+        queued_notifications.extend(queued);
+    }
+
+    queued_notifications
+}
+
+/// is_behind_paused_notification checks whether the notification is queued behind a paused
+/// notification, in which case its task must not run until that one is resumed.
+pub async fn is_behind_paused_notification(
+    glue: ArcGlue,
+    notification: &Notification,
+) -> Result<bool, NotificationError> {
+    let notifications = db::list_notification(glue)
+        .await
+        .map_err(NotificationError::Database)?;
+
+    Ok(is_behind(notification, &notifications))
+}
+
+/// is_behind follows the queue from the notification back to the first one,
+/// and checks whether any notification on the way is paused.
+fn is_behind(notification: &Notification, notifications: &[Notification]) -> bool {
+    let mut queued_after = notification.get_queued_after();
+
+    // every step visits another notification, unless the stored queue is broken
+    for _ in 0..notifications.len() {
+        let previous =
+            match queued_after.and_then(|id| notifications.iter().find(|n| n.get_id() == id)) {
+                Some(previous) => previous,
+                None => return false,
+            };
+        if previous.is_paused() {
+            return true;
+        }
+
+        queued_after = previous.get_queued_after();
+    }
+
+    false
+}
+
+/// update_and_respawn_notifications stores the notifications and reschedules their tasks.
+/// Paused notifications and the ones queued behind any paused notification are only stored,
+/// so that they stay stopped until that notification is resumed.
+async fn update_and_respawn_notifications<I>(
+    notifications: I,
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+) -> Result<(), NotificationError>
+where
+    I: IntoIterator<Item = Notification>,
+{
+    let mut stored_notifications = Vec::new();
+    for notification in notifications {
+        db::update_notification(glue.clone(), &notification)
+            .await
+            .map_err(NotificationError::Database)?;
+        stored_notifications.push(notification);
+    }
+
+    let notifications = db::list_notification(glue.clone())
+        .await
+        .map_err(NotificationError::Database)?;
+    for notification in stored_notifications {
+        if notification.is_paused() || is_behind(&notification, &notifications) {
+            continue;
+        }
+
+        respawn_notification(
+            configuration.clone(),
+            notification_task_map.clone(),
+            glue.clone(),
+            event_tx.clone(),
+            notification,
+        );
+    }
 
     Ok(())
 }
 
+/// respawn_notification replaces the task of the notification with a new one
+/// which follows the timestamps of the given notification.
+fn respawn_notification(
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+    notification: Notification,
+) {
+    let id = notification.get_id();
+    let handle = spawn_notification(
+        configuration,
+        notification_task_map.clone(),
+        glue,
+        event_tx,
+        notification,
+    );
+
+    if let Some(previous_handle) = notification_task_map.lock().unwrap().insert(id, handle) {
+        previous_handle.abort();
+    }
+}
+
+/// resume_notifications respawns the tasks of the notifications stored in the database,
+/// which is useful when a persistent storage is used and pomodoro is restarted.
+/// Notifications expired while pomodoro was not running are archived.
+/// Depending on the configuration, the missed notifications are fired in the background.
+/// Paused notifications and the ones queued behind them are left until they are resumed.
+/// It returns the number of resumed and archived notifications.
+pub async fn resume_notifications(
+    configuration: Arc<Configuration>,
+    notification_task_map: ArcTaskMap,
+    glue: ArcGlue,
+    event_tx: EventSender,
+) -> Result<(usize, usize), NotificationError> {
+    let should_fire =
+        configuration.get_missed_notification_policy() == MissedNotificationPolicy::Fire;
+    let now = Utc::now();
+
+    let (mut resumed, mut archived) = (0, 0);
+    let mut missed_notifications = Vec::new();
+    let notifications = db::list_notification(glue.clone())
+        .await
+        .map_err(NotificationError::Database)?;
+    let stopped: Vec<bool> = notifications
+        .iter()
+        .map(|n| n.is_paused() || is_behind(n, &notifications))
+        .collect();
+
+    for (notification, stopped) in notifications.into_iter().zip(stopped) {
+        let (id, _, work_time, break_time, _, work_expired_at, _) = notification.get_values();
+
+        if stopped {
+            continue;
+        }
+
+        if notification.get_last_expired_at() <= now {
+            if should_fire {
+                let kind = if break_time > 0 {
+                    EventKind::BreakDone
+                } else {
+                    EventKind::WorkDone
+                };
+                missed_notifications.push((kind, notification));
+            }
+
+            db::delete_and_archive_notification(glue.clone(), id)
+                .await
+                .map_err(NotificationError::Database)?;
+            archived += 1;
+            continue;
+        }
+
+        // work time is expired but break time is still remaining
+        if should_fire && work_time > 0 && work_expired_at <= now {
+            missed_notifications.push((EventKind::WorkDone, notification.clone()));
+        }
+
+        let handle = spawn_notification(
+            configuration.clone(),
+            notification_task_map.clone(),
+            glue.clone(),
+            event_tx.clone(),
+            notification,
+        );
+        notification_task_map.lock().unwrap().insert(id, handle);
+        resumed += 1;
+    }
+
+    fire_missed_notifications(configuration, missed_notifications);
+
+    Ok((resumed, archived))
+}
+
+/// fire_missed_notifications sends the notifications missed while pomodoro was not running.
+/// They are sent from a task so that the start up does not wait for the notifiers.
+fn fire_missed_notifications(
+    configuration: Arc<Configuration>,
+    missed_notifications: Vec<(EventKind, Notification)>,
+) {
+    if missed_notifications.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        for (kind, notification) in missed_notifications {
+            let result = if kind == EventKind::BreakDone {
+                notify_break(&configuration, &notification).await
+            } else {
+                notify_work(&configuration, &notification).await
+            };
+            if let Ok(report) = result {
+                info!("\n{}", report);
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::PathBuf;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
 
     use chrono::DateTime;
     use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
     use clap::Command;
     use gluesql::core::data::Value;
+    use serde_json::json;
     use tabled::Tabled;
 
-    use crate::command::add_args_for_create_subcommand;
-    use crate::configuration::load_configuration;
+    use crate::command::{add_args_for_create_subcommand, add_args_for_cycle_subcommand};
+    use crate::configuration::{load_configuration, Configuration};
+    use crate::db;
 
-    use super::get_new_notification;
-    use super::Notification;
+    use super::{
+        extend_notification, get_new_cycle_notifications, get_new_notification,
+        get_queued_notifications, is_behind, resume_notifications, skip_notification,
+        snooze_notification,
+    };
+    use super::{Notification, NotificationState};
+    use crate::error::NotificationError;
+    use crate::event::EventKind;
 
     #[test]
     fn test_notification() {
         let now = Utc::now();
-        let notification1 = Notification::new(0, 25, 5, now);
+        let notification1 = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
         assert_eq!(
             now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
             notification1
@@ -357,15 +1397,20 @@ mod tests {
             let row = vec![
                 Value::I64(0),
                 Value::Str("sample".to_string()),
-                Value::I64(25),
-                Value::I64(5),
+                Value::I64(25 * 60),
+                Value::I64(5 * 60),
                 Value::Timestamp(naive_date_time),
                 Value::Timestamp(naive_date_time + Duration::minutes(25)),
                 Value::Timestamp(naive_date_time + Duration::minutes(30)),
+                Value::Null,
+                Value::Bool(false),
+                Value::I64(0),
+                Value::I64(0),
+                Value::Null,
             ]
             .into();
 
-            Notification::convert_to_notification(row)
+            Notification::convert_to_notification(row).unwrap()
         };
 
         let test_cases = vec![
@@ -380,8 +1425,8 @@ mod tests {
             let (id, desc, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
             assert_eq!(0, id, "failed: {}", test_case);
             assert_eq!("sample", desc, "failed: {}", test_case);
-            assert_eq!(25, wt, "failed: {}", test_case);
-            assert_eq!(5, bt, "failed: {}", test_case);
+            assert_eq!(25 * 60, wt, "failed: {}", test_case);
+            assert_eq!(5 * 60, bt, "failed: {}", test_case);
 
             let start_at = notification.get_start_at();
 
@@ -403,19 +1448,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_convert_invalid_row() {
+        let row = vec![Value::I64(0), Value::I64(0), Value::I64(25), Value::I64(5)].into();
+
+        let result = Notification::convert_to_notification(row);
+        assert!(result.is_err());
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("description type mismatch"));
+    }
+
     #[test]
     fn test_notification_tabled_impl() {
         let now = Utc::now();
-        let notification = Notification::new(0, 25, 5, now);
+        let notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
 
         let fields = notification.fields();
-        assert_eq!(8, fields.len());
+        assert_eq!(11, fields.len());
+        assert_eq!("work", fields[1]);
+        assert_eq!("N/A", fields[2]);
+        assert_eq!("N/A", fields[3]);
+
+        // scheduled notification shows when it starts
+        let notification =
+            Notification::new(1, "sample", 25 * 60, 5 * 60, now + Duration::minutes(90));
+        let fields = notification.fields();
+        assert_eq!("pending", fields[1]);
+        assert!(fields[3].starts_with("1h"), "starts_in: {}", fields[3]);
 
         let headers = Notification::headers();
-        assert_eq!(8, headers.len());
+        assert_eq!(11, headers.len());
         assert_eq!(
             vec![
                 "id".to_string(),
+                "state".to_string(),
+                "cycle".to_string(),
+                "starts_in".to_string(),
                 "work_remaining (min)".to_string(),
                 "break_remaining (min)".to_string(),
                 "start_at".to_string(),
@@ -427,6 +1498,341 @@ mod tests {
             headers
         );
     }
+
+    #[test]
+    fn test_to_record() {
+        let created_at = Utc.ymd(2023, 3, 1).and_hms(9, 0, 0);
+        let notification =
+            Notification::new(3, "review PR", 25 * 60, 5 * 60, created_at).with_cycle(1, 4);
+
+        let record =
+            serde_json::to_value(notification.to_record(created_at + Duration::minutes(10)))
+                .unwrap();
+        assert_eq!(
+            json!({
+                "id": 3,
+                "description": "review PR",
+                "state": "work",
+                "cycle": { "index": 1, "total": 4 },
+                "work_time": 1500,
+                "break_time": 300,
+                "work_remaining": 900,
+                "break_remaining": 1200,
+                "percentage": 40,
+                "started_at": "2023-03-01T09:00:00Z",
+                "work_expired_at": "2023-03-01T09:25:00Z",
+                "break_expired_at": "2023-03-01T09:30:00Z",
+                "paused_at": null,
+                "cut_short": false,
+            }),
+            record
+        );
+
+        // the remaining time is frozen while paused, and never negative
+        let mut notification = Notification::new(4, "", 25 * 60, 0, created_at);
+        notification.pause(created_at + Duration::minutes(20));
+        let record = notification.to_record(created_at + Duration::hours(1));
+        assert_eq!(NotificationState::Paused, record.state);
+        assert_eq!(300, record.work_remaining);
+        assert_eq!(0, record.break_remaining);
+        assert_eq!(Some(String::from("2023-03-01T09:20:00Z")), record.paused_at);
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let now = Utc::now();
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        assert_eq!(
+            NotificationState::Pending,
+            notification.get_state(now - Duration::minutes(1))
+        );
+        assert_eq!(
+            NotificationState::Work,
+            notification.get_state(now + Duration::minutes(10))
+        );
+        assert_eq!(
+            NotificationState::Break,
+            notification.get_state(now + Duration::minutes(27))
+        );
+
+        // paused during the work, both phases are postponed
+        notification.pause(now + Duration::minutes(10));
+        assert!(notification.is_paused());
+        assert_eq!(
+            NotificationState::Paused,
+            notification.get_state(now + Duration::minutes(10))
+        );
+        assert_eq!("paused", notification.fields()[1]);
+
+        notification.resume(now + Duration::minutes(20));
+        assert!(!notification.is_paused());
+        let (_, _, _, _, created_at, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!(now, created_at);
+        assert_eq!(now + Duration::minutes(35), w_expired_at);
+        assert_eq!(now + Duration::minutes(40), b_expired_at);
+        assert_eq!(now + Duration::minutes(10), notification.get_start_at());
+
+        // paused during the break, only the break is postponed
+        notification.pause(now + Duration::minutes(37));
+        notification.resume(now + Duration::minutes(40));
+        let (_, _, _, _, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!(now + Duration::minutes(35), w_expired_at);
+        assert_eq!(now + Duration::minutes(43), b_expired_at);
+        assert_eq!(now + Duration::minutes(10), notification.get_start_at());
+    }
+
+    #[test]
+    fn test_queued_notifications() {
+        let now = Utc::now();
+        let get_notifications = || {
+            vec![
+                Notification::new(1, "", 25 * 60, 5 * 60, now),
+                Notification::new(2, "", 25 * 60, 5 * 60, now + Duration::minutes(30))
+                    .with_queued_after(Some(1)),
+                Notification::new(3, "", 25 * 60, 5 * 60, now + Duration::minutes(60))
+                    .with_queued_after(Some(2)),
+                // scheduled with --at, so not queued even though it starts later
+                Notification::new(4, "", 25 * 60, 5 * 60, now + Duration::minutes(90)),
+                Notification::new(5, "", 25 * 60, 5 * 60, now + Duration::minutes(120))
+                    .with_queued_after(Some(4)),
+            ]
+        };
+        let get_ids = |notifications: Vec<Notification>| {
+            let mut ids: Vec<u16> = notifications.iter().map(|n| n.get_id()).collect();
+            ids.sort_unstable();
+            ids
+        };
+
+        assert_eq!(
+            vec![2, 3],
+            get_ids(get_queued_notifications(1, get_notifications()))
+        );
+        assert_eq!(
+            vec![5],
+            get_ids(get_queued_notifications(4, get_notifications()))
+        );
+        assert!(get_queued_notifications(3, get_notifications()).is_empty());
+
+        // the paused one and the ones behind it are moved on its own resume
+        let mut notifications = get_notifications();
+        notifications[1].pause(now + Duration::minutes(10));
+        assert!(get_queued_notifications(1, notifications).is_empty());
+
+        let mut notifications = get_notifications();
+        notifications[0].pause(now + Duration::minutes(10));
+        let behind: Vec<bool> = notifications
+            .iter()
+            .map(|n| is_behind(n, &notifications))
+            .collect();
+        assert_eq!(vec![false, true, true, false, false], behind);
+    }
+
+    #[test]
+    fn test_extend_and_snooze() {
+        let now = Utc::now();
+
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification
+            .extend(now + Duration::minutes(10), 5 * 60)
+            .unwrap();
+        let (_, _, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!((30 * 60, 5 * 60), (wt, bt));
+        assert_eq!(now + Duration::minutes(30), w_expired_at);
+        assert_eq!(now + Duration::minutes(35), b_expired_at);
+
+        notification
+            .extend(now + Duration::minutes(32), 5 * 60)
+            .unwrap();
+        let (_, _, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!((30 * 60, 10 * 60), (wt, bt));
+        assert_eq!(now + Duration::minutes(30), w_expired_at);
+        assert_eq!(now + Duration::minutes(40), b_expired_at);
+        assert_eq!(now, notification.get_start_at());
+
+        // without work time, the break is extended even before it starts
+        let mut notification = Notification::new(0, "sample", 0, 5 * 60, now);
+        notification
+            .extend(now - Duration::minutes(1), 5 * 60)
+            .unwrap();
+        let (_, _, wt, bt, _, _, b_expired_at) = notification.get_values();
+        assert_eq!((0, 10 * 60), (wt, bt));
+        assert_eq!(now + Duration::minutes(10), b_expired_at);
+
+        notification.snooze(45).unwrap();
+        let (_, _, _, bt, _, _, b_expired_at) = notification.get_values();
+        assert_eq!(10 * 60 + 45, bt);
+        assert_eq!(now + Duration::seconds(10 * 60 + 45), b_expired_at);
+
+        // the notification is left as it is when the time overflows
+        let mut notification = Notification::new(0, "sample", u32::MAX - 60, 5 * 60, now);
+        assert!(notification
+            .extend(now + Duration::minutes(10), 5 * 60)
+            .is_err());
+        assert!(notification.snooze(u32::MAX).is_err());
+        let (_, _, wt, bt, _, _, _) = notification.get_values();
+        assert_eq!((u32::MAX - 60, 5 * 60), (wt, bt));
+    }
+
+    #[test]
+    fn test_skip() {
+        let now = Utc::now();
+
+        // skipping the work starts the break right away
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.skip(now + Duration::minutes(10));
+        assert!(notification.is_cut_short());
+        let (_, _, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!((10 * 60, 5 * 60), (wt, bt));
+        assert_eq!(now + Duration::minutes(10), w_expired_at);
+        assert_eq!(now + Duration::minutes(15), b_expired_at);
+        assert_eq!(now, notification.get_start_at());
+
+        // skipping the break finishes the notification
+        notification.skip(now + Duration::seconds(12 * 60 + 30));
+        let (_, _, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!((10 * 60, 2 * 60 + 30), (wt, bt));
+        assert_eq!(now + Duration::minutes(10), w_expired_at);
+        assert_eq!(now + Duration::seconds(12 * 60 + 30), b_expired_at);
+        assert_eq!(
+            NotificationState::Done,
+            notification.get_state(b_expired_at)
+        );
+    }
+
+    #[test]
+    fn test_cut_off() {
+        let now = Utc::now();
+
+        // cut during the work, the break is dropped
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.cut_off(now + Duration::minutes(10));
+        assert!(notification.is_cut_short());
+        let (_, _, wt, bt, _, w_expired_at, b_expired_at) = notification.get_values();
+        assert_eq!((10 * 60, 0), (wt, bt));
+        assert_eq!(now + Duration::minutes(10), w_expired_at);
+        assert_eq!(now + Duration::minutes(10), b_expired_at);
+        assert_eq!(now, notification.get_start_at());
+
+        // cut during the break, the work is kept
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.cut_off(now + Duration::minutes(27));
+        let (_, _, wt, bt, _, _, _) = notification.get_values();
+        assert_eq!((25 * 60, 2 * 60), (wt, bt));
+
+        // a paused notification is cut when it was paused
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.pause(now + Duration::minutes(5));
+        notification.cut_off(now + Duration::hours(1));
+        assert!(!notification.is_paused());
+        let (_, _, wt, bt, _, _, _) = notification.get_values();
+        assert_eq!((5 * 60, 0), (wt, bt));
+
+        // nothing is spent on a pending notification
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.cut_off(now - Duration::minutes(1));
+        let (_, _, wt, bt, _, _, _) = notification.get_values();
+        assert_eq!((0, 0), (wt, bt));
+        assert_eq!(now, notification.get_start_at());
+
+        // a finished notification is left as it is
+        let mut notification = Notification::new(0, "sample", 25 * 60, 5 * 60, now);
+        notification.cut_off(now + Duration::hours(1));
+        assert!(!notification.is_cut_short());
+        let (_, _, wt, bt, _, _, _) = notification.get_values();
+        assert_eq!((25 * 60, 5 * 60), (wt, bt));
+    }
+
+    #[test]
+    fn test_create_cycle_notifications() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("resources/test/mock_configuration.json");
+        let (mock_configuration, _) = load_configuration(path.to_str()).unwrap();
+        let configuration = Arc::new(mock_configuration);
+
+        let mut id_manager = 0;
+        let now = Utc::now();
+
+        // mock configuration has 30 minutes work, 10 minutes break,
+        // 20 minutes long break and the long break for every 3rd pomodoro
+        let matches = add_args_for_cycle_subcommand(Command::new("myapp"))
+            .get_matches_from("myapp -n 4 -m focus".split_whitespace());
+        let notifications = get_new_cycle_notifications(
+            &matches,
+            &mut id_manager,
+            now,
+            None,
+            configuration.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(4, notifications.len());
+        assert_eq!(4, id_manager);
+
+        let mut start_at = now;
+        for (i, notification) in notifications.iter().enumerate() {
+            let index = i as u16 + 1;
+            let (id, desc, wt, bt, _, _, _) = notification.get_values();
+
+            assert_eq!(i as u16, id);
+            assert_eq!("focus", desc);
+            assert_eq!(30 * 60, wt);
+            assert_eq!(if index == 3 { 20 * 60 } else { 10 * 60 }, bt);
+            assert_eq!(Some((index, 4)), notification.get_cycle());
+            assert_eq!(start_at, notification.get_start_at());
+            assert_eq!(id.checked_sub(1), notification.get_queued_after());
+
+            start_at = notification.get_last_expired_at();
+        }
+        assert_eq!("1/4", notifications[0].fields()[2]);
+
+        // count defaults to the long break interval
+        let matches = add_args_for_cycle_subcommand(Command::new("myapp"))
+            .get_matches_from("myapp -w 25 -b 5 -l 0.5h".split_whitespace());
+        let notifications = get_new_cycle_notifications(
+            &matches,
+            &mut id_manager,
+            now,
+            None,
+            configuration.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(3, notifications.len());
+        let (_, _, wt, bt, _, _, _) = notifications[2].get_values();
+        assert_eq!((25 * 60, 30 * 60), (wt, bt));
+
+        let matches = add_args_for_cycle_subcommand(Command::new("myapp"))
+            .get_matches_from("myapp -n 0".split_whitespace());
+        let result = get_new_cycle_notifications(
+            &matches,
+            &mut id_manager,
+            now,
+            None,
+            configuration.clone(),
+        );
+        assert!(result.is_err());
+
+        // an invalid count is not replaced with the default
+        let matches = add_args_for_cycle_subcommand(Command::new("myapp"))
+            .get_matches_from("myapp -n four".split_whitespace());
+        let result = get_new_cycle_notifications(
+            &matches,
+            &mut id_manager,
+            now,
+            None,
+            configuration.clone(),
+        );
+        assert!(result.is_err());
+
+        // ids are not wrapped around
+        let mut id_manager = u16::MAX - 2;
+        let matches = add_args_for_cycle_subcommand(Command::new("myapp"))
+            .get_matches_from("myapp -n 4".split_whitespace());
+        let result =
+            get_new_cycle_notifications(&matches, &mut id_manager, now, None, configuration);
+        assert!(matches!(result, Err(NotificationError::IdExhausted)));
+    }
+
     #[test]
     fn test_create_notifications() {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -445,9 +1851,10 @@ mod tests {
             command: &'static str,
             use_mock_configuration: bool,
             expect_notification_error: bool,
-            expected_work_time: u16,
-            expected_break_time: u16,
+            expected_work_time: u32,
+            expected_break_time: u32,
             expected_created_at: &'a DateTime<Utc>,
+            expected_description: &'static str,
         }
 
         let test_cases: Vec<TestCase> = vec![
@@ -456,90 +1863,120 @@ mod tests {
                 command: "myapp --default",
                 use_mock_configuration: true,
                 expect_notification_error: false,
-                expected_work_time: 30,
-                expected_break_time: 10,
+                expected_work_time: 30 * 60,
+                expected_break_time: 10 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "default flags without configuration",
                 command: "myapp --default",
                 use_mock_configuration: false,
                 expect_notification_error: false,
-                expected_work_time: 25,
-                expected_break_time: 5,
+                expected_work_time: 25 * 60,
+                expected_break_time: 5 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "no flags with configuration",
                 command: "myapp",
                 use_mock_configuration: true,
                 expect_notification_error: false,
-                expected_work_time: 30,
-                expected_break_time: 10,
+                expected_work_time: 30 * 60,
+                expected_break_time: 10 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "no flags without configuration",
                 command: "myapp",
                 use_mock_configuration: false,
                 expect_notification_error: false,
-                expected_work_time: 25,
-                expected_break_time: 5,
+                expected_work_time: 25 * 60,
+                expected_break_time: 5 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "both work and break flag specified with configuration",
                 command: "myapp -w 50 -b 25",
                 use_mock_configuration: true,
                 expect_notification_error: false,
-                expected_work_time: 50,
-                expected_break_time: 25,
+                expected_work_time: 50 * 60,
+                expected_break_time: 25 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "only work flag specified with configuration",
                 command: "myapp -w 50",
                 use_mock_configuration: true,
                 expect_notification_error: false,
-                expected_work_time: 50,
-                expected_break_time: 10,
+                expected_work_time: 50 * 60,
+                expected_break_time: 10 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "only break flag specified with configuration",
                 command: "myapp -b 25",
                 use_mock_configuration: true,
                 expect_notification_error: false,
-                expected_work_time: 30,
-                expected_break_time: 25,
+                expected_work_time: 30 * 60,
+                expected_break_time: 25 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "both work and break flag specified without configuration",
                 command: "myapp -w 50 -b 25",
                 use_mock_configuration: false,
                 expect_notification_error: false,
-                expected_work_time: 50,
-                expected_break_time: 25,
+                expected_work_time: 50 * 60,
+                expected_break_time: 25 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "only work flag specified without configuration",
                 command: "myapp -w 50",
                 use_mock_configuration: false,
                 expect_notification_error: false,
-                expected_work_time: 50,
-                expected_break_time: 5,
+                expected_work_time: 50 * 60,
+                expected_break_time: 5 * 60,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "only break flag specified without configuration",
                 command: "myapp -b 25",
                 use_mock_configuration: false,
                 expect_notification_error: false,
-                expected_work_time: 25,
-                expected_break_time: 25,
+                expected_work_time: 25 * 60,
+                expected_break_time: 25 * 60,
+                expected_created_at: &now,
+                expected_description: "",
+            },
+            TestCase {
+                desc: "duration syntax specified without configuration",
+                command: "myapp -w 1h30m -b 45s",
+                use_mock_configuration: false,
+                expect_notification_error: false,
+                expected_work_time: 90 * 60,
+                expected_break_time: 45,
+                expected_created_at: &now,
+                expected_description: "",
+            },
+            TestCase {
+                desc: "message flag specified without configuration",
+                command: "myapp -w 50 -m review",
+                use_mock_configuration: false,
+                expect_notification_error: false,
+                expected_work_time: 50 * 60,
+                expected_break_time: 5 * 60,
                 expected_created_at: &now,
+                expected_description: "review",
             },
             TestCase {
                 desc: "both work and break are specified as zero with configuration",
@@ -549,6 +1986,7 @@ mod tests {
                 expected_work_time: 0,
                 expected_break_time: 0,
                 expected_created_at: &now,
+                expected_description: "",
             },
             TestCase {
                 desc: "both work and break are specified as zero without configuration",
@@ -558,6 +1996,17 @@ mod tests {
                 expected_work_time: 0,
                 expected_break_time: 0,
                 expected_created_at: &now,
+                expected_description: "",
+            },
+            TestCase {
+                desc: "invalid duration specified without configuration",
+                command: "myapp -w 25x",
+                use_mock_configuration: false,
+                expect_notification_error: true,
+                expected_work_time: 0,
+                expected_break_time: 0,
+                expected_created_at: &now,
+                expected_description: "",
             },
         ];
 
@@ -580,9 +2029,15 @@ mod tests {
                 continue;
             }
 
-            let (id, _, work_time, break_time, created_at, _, _) =
-                notification.unwrap().get_values();
+            let notification = notification.unwrap();
+            let (id, description, work_time, break_time, created_at, _, _) =
+                notification.get_values();
             assert_eq!(idx as u16, id, "Test case: {}", tc.desc);
+            assert_eq!(
+                tc.expected_description, description,
+                "Test case: {}",
+                tc.desc
+            );
             assert_eq!(tc.expected_work_time, work_time, "Test case: {}", tc.desc);
             assert_eq!(tc.expected_break_time, break_time, "Test case: {}", tc.desc);
             assert_eq!(
@@ -611,15 +2066,20 @@ mod tests {
             let row = vec![
                 Value::I64(0),
                 Value::Str("sample".to_string()),
-                Value::I64(25),
-                Value::I64(5),
+                Value::I64(25 * 60),
+                Value::I64(5 * 60),
                 Value::Timestamp(naive_date_time),
                 Value::Timestamp(naive_date_time + Duration::minutes(25)),
                 Value::Timestamp(naive_date_time + Duration::minutes(30)),
+                Value::Null,
+                Value::Bool(false),
+                Value::I64(0),
+                Value::I64(0),
+                Value::Null,
             ]
             .into();
 
-            Notification::convert_to_notification(row)
+            Notification::convert_to_notification(row).unwrap()
         };
 
         // notification has no work time
@@ -631,15 +2091,20 @@ mod tests {
             let row = vec![
                 Value::I64(0),
                 Value::Str("sample".to_string()),
-                Value::I64(25),
-                Value::I64(5),
+                Value::I64(25 * 60),
+                Value::I64(5 * 60),
                 Value::Timestamp(naive_date_time),
                 Value::Timestamp(naive_date_time + Duration::minutes(25)),
                 Value::Timestamp(naive_date_time + Duration::minutes(30)),
+                Value::Null,
+                Value::Bool(false),
+                Value::I64(0),
+                Value::I64(0),
+                Value::Null,
             ]
             .into();
 
-            Notification::convert_to_notification(row)
+            Notification::convert_to_notification(row).unwrap()
         };
 
         // notification time hasn't started yet
@@ -651,15 +2116,20 @@ mod tests {
             let row = vec![
                 Value::I64(0),
                 Value::Str("sample".to_string()),
-                Value::I64(25),
-                Value::I64(5),
+                Value::I64(25 * 60),
+                Value::I64(5 * 60),
                 Value::Timestamp(naive_date_time),
                 Value::Timestamp(naive_date_time + Duration::minutes(25)),
                 Value::Timestamp(naive_date_time + Duration::minutes(30)),
+                Value::Null,
+                Value::Bool(false),
+                Value::I64(0),
+                Value::I64(0),
+                Value::Null,
             ]
             .into();
 
-            Notification::convert_to_notification(row)
+            Notification::convert_to_notification(row).unwrap()
         };
 
         let expected_value_1 = "20";
@@ -679,4 +2149,118 @@ mod tests {
             expected_value_3
         );
     }
+
+    #[tokio::test]
+    async fn test_resume_notifications() {
+        let glue = Arc::new(Mutex::new(db::get_memory_glue()));
+        db::initialize(glue.clone()).await.unwrap();
+        let task_map = Arc::new(Mutex::new(HashMap::new()));
+
+        let now = Utc::now();
+        // expired while pomodoro was not running
+        let expired = Notification::new(1, "sample", 25 * 60, 5 * 60, now - Duration::minutes(60));
+        db::create_notification(glue.clone(), &expired)
+            .await
+            .unwrap();
+        // still in break time
+        let in_break = Notification::new(2, "sample", 25 * 60, 5 * 60, now - Duration::minutes(27));
+        db::create_notification(glue.clone(), &in_break)
+            .await
+            .unwrap();
+        // still in work time
+        let in_work = Notification::new(3, "sample", 25 * 60, 5 * 60, now - Duration::minutes(10));
+        db::create_notification(glue.clone(), &in_work)
+            .await
+            .unwrap();
+
+        let (resumed, archived) = resume_notifications(
+            Arc::new(Configuration::default()),
+            task_map.clone(),
+            glue.clone(),
+            mpsc::unbounded_channel().0,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(2, resumed);
+        assert_eq!(1, archived);
+
+        {
+            let task_map = task_map.lock().unwrap();
+            assert!(!task_map.contains_key(&1));
+            assert!(task_map.contains_key(&2));
+            assert!(task_map.contains_key(&3));
+        }
+
+        let archived_notifications = db::list_archived_notification(glue.clone()).await.unwrap();
+        assert_eq!(1, archived_notifications.len());
+        assert_eq!(1, archived_notifications[0].get_id());
+    }
+
+    #[tokio::test]
+    async fn test_extend_and_snooze_by_zero() {
+        // rejected before the notification is looked up
+        let glue = Arc::new(Mutex::new(db::get_memory_glue()));
+        let task_map = Arc::new(Mutex::new(HashMap::new()));
+        let configuration = Arc::new(Configuration::default());
+
+        let result = extend_notification(
+            1,
+            0,
+            configuration.clone(),
+            task_map.clone(),
+            glue.clone(),
+            mpsc::unbounded_channel().0,
+        )
+        .await;
+        assert!(matches!(result, Err(NotificationError::EmptyTimeValues)));
+
+        let result = snooze_notification(
+            1,
+            0,
+            configuration,
+            task_map,
+            glue,
+            mpsc::unbounded_channel().0,
+            &mut 2,
+        )
+        .await;
+        assert!(matches!(result, Err(NotificationError::EmptyTimeValues)));
+    }
+
+    #[tokio::test]
+    async fn test_skip_notification_publishes_events() {
+        let glue = Arc::new(Mutex::new(db::get_memory_glue()));
+        db::initialize(glue.clone()).await.unwrap();
+        let task_map = Arc::new(Mutex::new(HashMap::new()));
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let configuration = Arc::new(Configuration::default());
+
+        let notification = Notification::new(1, "sample", 25 * 60, 5 * 60, Utc::now());
+        db::create_notification(glue.clone(), &notification)
+            .await
+            .unwrap();
+
+        for kind in [EventKind::WorkDone, EventKind::BreakDone] {
+            skip_notification(
+                1,
+                true,
+                configuration.clone(),
+                task_map.clone(),
+                glue.clone(),
+                event_tx.clone(),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(kind, event_rx.try_recv().unwrap().get_kind());
+        }
+
+        // no phase is left, so it is archived
+        assert!(db::read_notification(glue.clone(), 1)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(event_rx.try_recv().is_err());
+    }
 }
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+#[cfg(target_os = "linux")]
+use notify_rust::Hint;
+use notify_rust::{Notification as NR_Notification, Timeout as NR_Timeout};
+
+use super::{Notifier, NotifyMessage};
+use crate::error::{NotificationError, NotifyResult};
+
+/// DesktopNotifier sends notification to desktop.
+/// use notify-rust library for desktop notification
+pub struct DesktopNotifier;
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> String {
+        String::from("Desktop")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let mut notification = NR_Notification::new();
+        let notification = notification
+            .summary(&message.summary)
+            .body(&message.body)
+            .appname("pomodoro")
+            .timeout(NR_Timeout::Milliseconds(5000));
+
+        #[cfg(target_os = "linux")]
+        notification
+            .hint(Hint::Category("im.received".to_owned()))
+            .sound_name("message-new-instant");
+
+        notification
+            .show()
+            .map(|_| ())
+            .map_err(NotificationError::Desktop)
+    }
+}
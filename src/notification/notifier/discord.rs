@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::Configuration;
+use crate::error::{NotificationError, NotifyResult};
+
+/// DiscordNotifier sends notification to discord using the webhook
+pub struct DiscordNotifier {
+    webhook_url: String,
+}
+
+impl DiscordNotifier {
+    /// from_configuration returns the notifier if the webhook url is specified in the configuration
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        configuration
+            .get_discord_webhook_url()
+            .clone()
+            .map(|webhook_url| DiscordNotifier { webhook_url })
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> String {
+        String::from("Discord")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let body = json!({ "content": message.text }).to_string();
+
+        let client = get_http_client();
+        let resp = client
+            .post(&self.webhook_url)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await;
+
+        debug!("resp: {:?}", resp);
+
+        resp.map(|_| ()).map_err(NotificationError::Discord)
+    }
+}
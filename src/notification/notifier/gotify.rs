@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::Configuration;
+use crate::error::{NotificationError, NotifyResult};
+
+// the android app makes a sound from the priority 4 and shows a heads-up notification from 8,
+// so the default makes a sound without interrupting
+const DEFAULT_PRIORITY: u8 = 5;
+
+/// GotifyNotifier sends the notification to the application of the gotify server
+pub struct GotifyNotifier {
+    server_url: Option<String>,
+    app_token: Option<String>,
+    priority: Option<u8>,
+}
+
+impl GotifyNotifier {
+    /// from_configuration returns None unless the server url or the app token is set.
+    /// A half configured notifier is kept so that notify reports the missing value.
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        let server_url = configuration.get_gotify_server_url();
+        let app_token = configuration.get_gotify_app_token();
+
+        if server_url.is_none() && app_token.is_none() {
+            return None;
+        }
+
+        Some(GotifyNotifier {
+            server_url: server_url.clone(),
+            app_token: app_token.clone(),
+            priority: configuration.get_gotify_priority(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for GotifyNotifier {
+    fn name(&self) -> String {
+        String::from("Gotify")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let (server_url, app_token) = match (&self.server_url, &self.app_token) {
+            (Some(server_url), Some(app_token)) => (server_url, app_token),
+            _ => {
+                debug!("server url or app token is none");
+                return Err(NotificationError::EmptyConfiguration);
+            }
+        };
+
+        let body = json!({
+            "title": message.summary,
+            "message": message.text,
+            "priority": self.priority.unwrap_or(DEFAULT_PRIORITY)
+        })
+        .to_string();
+
+        let client = get_http_client();
+        let resp = client
+            .post(format!("{}/message", server_url.trim_end_matches('/')))
+            .header("Content-Type", "application/json")
+            .header("X-Gotify-Key", app_token)
+            .body(body)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+
+        debug!("resp: {:?}", resp);
+
+        resp.map(|_| ()).map_err(NotificationError::Gotify)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    use super::GotifyNotifier;
+    use crate::error::NotificationError;
+    use crate::notification::notifier::stub::{get_message, spawn_stub_server};
+    use crate::notification::notifier::Notifier;
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, rx) = spawn_stub_server(200, r#"{"id": 1}"#);
+
+        let notifier = GotifyNotifier {
+            server_url: Some(format!("{}/", url)),
+            app_token: Some(String::from("secret")),
+            priority: None,
+        };
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("/message", request.path);
+        assert_eq!(Some("secret"), request.get_header("x-gotify-key"));
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            json!({
+                "title": "Break time done!",
+                "message": "Break time finished. Now back to work!",
+                "priority": 5
+            }),
+            body
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_error() {
+        let (url, _rx) = spawn_stub_server(401, r#"{"error": "Unauthorized"}"#);
+
+        let notifier = GotifyNotifier {
+            server_url: Some(url),
+            app_token: Some(String::from("wrong")),
+            priority: Some(8),
+        };
+        assert!(matches!(
+            notifier.notify(&get_message()).await,
+            Err(NotificationError::Gotify(_))
+        ));
+    }
+}
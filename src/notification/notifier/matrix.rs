@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Url;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::Configuration;
+use crate::error::{NotificationError, NotifyResult};
+
+/// MatrixNotifier sends notification to the room of matrix as m.room.message event
+pub struct MatrixNotifier {
+    homeserver_url: Option<String>,
+    access_token: Option<String>,
+    room_id: Option<String>,
+}
+
+impl MatrixNotifier {
+    /// from_configuration returns None if none of the homeserver url, the access token and the room id is set
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        let homeserver_url = configuration.get_matrix_homeserver_url();
+        let access_token = configuration.get_matrix_access_token();
+        let room_id = configuration.get_matrix_room_id();
+
+        if homeserver_url.is_none() && access_token.is_none() && room_id.is_none() {
+            return None;
+        }
+
+        Some(MatrixNotifier {
+            homeserver_url: homeserver_url.clone(),
+            access_token: access_token.clone(),
+            room_id: room_id.clone(),
+        })
+    }
+}
+
+/// get_send_url returns the url of the send event api.
+/// The room id and the transaction id are percent encoded as path segments.
+fn get_send_url(
+    homeserver_url: &str,
+    room_id: &str,
+    txn_id: &str,
+) -> Result<Url, NotificationError> {
+    let invalid_url =
+        || NotificationError::InvalidMatrix(format!("invalid homeserver url: {}", homeserver_url));
+
+    let mut url = Url::parse(homeserver_url).map_err(|_| invalid_url())?;
+    url.path_segments_mut()
+        .map_err(|_| invalid_url())?
+        .pop_if_empty()
+        .extend(&[
+            "_matrix",
+            "client",
+            "v3",
+            "rooms",
+            room_id,
+            "send",
+            "m.room.message",
+            txn_id,
+        ]);
+
+    Ok(url)
+}
+
+#[async_trait]
+impl Notifier for MatrixNotifier {
+    fn name(&self) -> String {
+        String::from("Matrix")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let (homeserver_url, access_token, room_id) =
+            match (&self.homeserver_url, &self.access_token, &self.room_id) {
+                (Some(homeserver_url), Some(access_token), Some(room_id)) => {
+                    (homeserver_url, access_token, room_id)
+                }
+                _ => {
+                    debug!("homeserver url, access token or room id is none");
+                    return Err(NotificationError::EmptyConfiguration);
+                }
+            };
+
+        // the homeserver ignores the retried request with the same transaction id
+        let txn_id = format!(
+            "pomodoro-{}-{}-{}",
+            message.id,
+            message.event.as_str(),
+            Utc::now().timestamp_nanos()
+        );
+        let url = get_send_url(homeserver_url, room_id, &txn_id)?;
+
+        let body = json!({
+            "msgtype": "m.text",
+            "body": message.text
+        })
+        .to_string();
+
+        let client = get_http_client();
+        let resp = client
+            .put(url)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .body(body)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+
+        debug!("resp: {:?}", resp);
+
+        resp.map(|_| ()).map_err(NotificationError::Matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    use super::{get_send_url, MatrixNotifier};
+    use crate::error::NotificationError;
+    use crate::notification::notifier::stub::{get_message, spawn_stub_server};
+    use crate::notification::notifier::Notifier;
+
+    #[test]
+    fn test_get_send_url() {
+        let url =
+            get_send_url("https://matrix.example.org/", "!room:example.org", "txn-1").unwrap();
+        assert_eq!(
+            "https://matrix.example.org/_matrix/client/v3/rooms/!room:example.org/send/m.room.message/txn-1",
+            url.as_str()
+        );
+
+        let url = get_send_url(
+            "https://example.org/matrix",
+            "#room id:example.org",
+            "txn-1",
+        )
+        .unwrap();
+        assert_eq!(
+            "https://example.org/matrix/_matrix/client/v3/rooms/%23room%20id:example.org/send/m.room.message/txn-1",
+            url.as_str()
+        );
+
+        assert!(get_send_url("matrix.example.org", "!room:example.org", "txn-1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, rx) = spawn_stub_server(200, r#"{"event_id": "$event"}"#);
+
+        let notifier = MatrixNotifier {
+            homeserver_url: Some(url),
+            access_token: Some(String::from("secret")),
+            room_id: Some(String::from("!room:example.org")),
+        };
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("PUT", request.method);
+        assert!(request.path.starts_with(
+            "/_matrix/client/v3/rooms/!room:example.org/send/m.room.message/pomodoro-2-break_done-"
+        ));
+        assert_eq!(Some("Bearer secret"), request.get_header("authorization"));
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            json!({
+                "msgtype": "m.text",
+                "body": "Break time finished. Now back to work!"
+            }),
+            body
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_error() {
+        let (url, _rx) = spawn_stub_server(403, r#"{"errcode": "M_FORBIDDEN"}"#);
+
+        let notifier = MatrixNotifier {
+            homeserver_url: Some(url),
+            access_token: Some(String::from("secret")),
+            room_id: Some(String::from("!room:example.org")),
+        };
+        assert!(matches!(
+            notifier.notify(&get_message()).await,
+            Err(NotificationError::Matrix(_))
+        ));
+    }
+}
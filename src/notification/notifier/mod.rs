@@ -0,0 +1,303 @@
+mod desktop;
+mod discord;
+mod gotify;
+mod matrix;
+mod ntfy;
+mod slack;
+#[cfg(test)]
+mod stub;
+mod telegram;
+mod webhook;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::configuration::Configuration;
+use crate::error::{NotificationError, NotifyResult};
+use crate::event::EventKind;
+use crate::notification::Notification;
+use crate::report::{self, NotifyReport};
+
+pub use desktop::DesktopNotifier;
+pub use discord::DiscordNotifier;
+pub use gotify::GotifyNotifier;
+pub use matrix::MatrixNotifier;
+pub use ntfy::NtfyNotifier;
+pub use slack::SlackNotifier;
+pub use telegram::TelegramNotifier;
+pub use webhook::WebhookNotifier;
+
+/// The time limit of a request to a channel, so that an unresponsive server does not
+/// keep the notification waiting
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The notification sent to the channels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyMessage {
+    pub event: EventKind,
+    pub id: u16,
+    pub description: String,
+    // work_time and break_time are in seconds
+    pub work_time: u32,
+    pub break_time: u32,
+    // when the phase is finished, or when the work is started for the work started event
+    pub finished_at: DateTime<Utc>,
+    // the summary and the body are shown in the desktop notification
+    pub summary: String,
+    pub body: String,
+    // the text is sent to the chat channels
+    pub text: String,
+}
+
+impl NotifyMessage {
+    pub fn new(event: EventKind, notification: &Notification) -> Self {
+        let (id, description, work_time, break_time, _, work_expired_at, break_expired_at) =
+            notification.get_values();
+        let finished_at = match event {
+            EventKind::WorkDone => work_expired_at,
+            EventKind::BreakDone => break_expired_at,
+            _ => notification.get_start_at(),
+        };
+
+        NotifyMessage {
+            event,
+            id,
+            description: description.to_string(),
+            work_time,
+            break_time,
+            finished_at,
+            summary: String::new(),
+            body: String::new(),
+            text: String::new(),
+        }
+    }
+
+    /// with_contents sets the summary and the body of the desktop notification,
+    /// and the text sent to the chat channels
+    pub fn with_contents(mut self, summary: &str, body: String, text: String) -> Self {
+        self.summary = summary.to_string();
+        self.body = body;
+        self.text = text;
+
+        self
+    }
+}
+
+/// Notifier sends the notification to a channel, e.g. desktop or slack
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// name is shown in the notify report
+    fn name(&self) -> String;
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult;
+}
+
+/// get_http_client returns the client for the channels reached over http
+fn get_http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("could not build the http client")
+}
+
+/// NotifierRegistry holds the notifiers of the configured channels
+#[derive(Default)]
+pub struct NotifierRegistry {
+    notifiers: Vec<Arc<dyn Notifier>>,
+}
+
+impl NotifierRegistry {
+    /// new returns the registry of the desktop and the channels specified in the configuration
+    pub fn new(configuration: &Configuration) -> Self {
+        let mut registry = NotifierRegistry::default();
+
+        registry.register(DesktopNotifier);
+        if let Some(notifier) = SlackNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        if let Some(notifier) = DiscordNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        if let Some(notifier) = TelegramNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        if let Some(notifier) = MatrixNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        if let Some(notifier) = NtfyNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        if let Some(notifier) = GotifyNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+        for notifier in WebhookNotifier::from_configuration(configuration) {
+            registry.register(notifier);
+        }
+
+        registry
+    }
+
+    pub fn register<N: Notifier + 'static>(&mut self, notifier: N) {
+        self.notifiers.push(Arc::new(notifier));
+    }
+
+    pub fn get_names(&self) -> Vec<String> {
+        self.notifiers.iter().map(|n| n.name()).collect()
+    }
+
+    /// notify sends the message to all channels at once.
+    /// The results are reported in the order the notifiers are registered.
+    pub async fn notify(&self, message: NotifyMessage) -> NotifyReport {
+        let message = Arc::new(message);
+
+        let handles: Vec<_> = self
+            .notifiers
+            .iter()
+            .map(|notifier| {
+                let (notifier, message) = (notifier.clone(), message.clone());
+
+                (
+                    notifier.name(),
+                    tokio::spawn(async move { notifier.notify(&message).await }),
+                )
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for (name, handle) in handles {
+            let result = handle
+                .await
+                .unwrap_or_else(|e| Err(NotificationError::Task(e)));
+            results.push((name, result));
+        }
+
+        report::generate_notify_report(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::stub::get_message;
+    use super::{
+        GotifyNotifier, MatrixNotifier, Notifier, NotifierRegistry, NotifyMessage, NtfyNotifier,
+        TelegramNotifier,
+    };
+    use crate::configuration::{load_configuration, Configuration};
+    use crate::error::{NotificationError, NotifyResult};
+    use crate::event::EventKind;
+    use crate::notification::Notification;
+
+    struct MockNotifier {
+        ok: bool,
+    }
+
+    #[async_trait]
+    impl Notifier for MockNotifier {
+        fn name(&self) -> String {
+            String::from(if self.ok { "Ok" } else { "Fail" })
+        }
+
+        async fn notify(&self, _: &NotifyMessage) -> NotifyResult {
+            if self.ok {
+                Ok(())
+            } else {
+                Err(NotificationError::EmptyConfiguration)
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_from_configuration() {
+        let registry = NotifierRegistry::new(&Configuration::default());
+        assert_eq!(vec!["Desktop"], registry.get_names());
+
+        let (configuration, _) =
+            load_configuration(Some("resources/test/mock_configuration.json")).unwrap();
+        let registry = NotifierRegistry::new(&configuration);
+        assert_eq!(
+            vec![
+                "Desktop",
+                "Slack",
+                "Discord",
+                "Telegram",
+                "Matrix",
+                "Ntfy",
+                "Gotify",
+                "Webhook (chat.example.com)"
+            ],
+            registry.get_names()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_partial_configuration() {
+        let configuration: Configuration = serde_json::from_value(json!({
+            "telegram": { "chat_id": "@pomodoro" },
+            "matrix": { "homeserver_url": "http://127.0.0.1:1", "room_id": "!room:example.org" },
+            "ntfy": { "server_url": "http://127.0.0.1:1" },
+            "gotify": { "server_url": "http://127.0.0.1:1" }
+        }))
+        .unwrap();
+
+        let notifiers: Vec<Box<dyn Notifier>> = vec![
+            Box::new(TelegramNotifier::from_configuration(&configuration).unwrap()),
+            Box::new(MatrixNotifier::from_configuration(&configuration).unwrap()),
+            Box::new(NtfyNotifier::from_configuration(&configuration).unwrap()),
+            Box::new(GotifyNotifier::from_configuration(&configuration).unwrap()),
+        ];
+        for notifier in notifiers {
+            assert!(
+                matches!(
+                    notifier.notify(&get_message()).await,
+                    Err(NotificationError::EmptyConfiguration)
+                ),
+                "{}",
+                notifier.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_notify_message() {
+        let start_at = Utc.ymd(2023, 3, 1).and_hms(9, 0, 0);
+        let notification = Notification::new(3, "review PR", 25 * 60, 5 * 60, start_at);
+
+        let message = NotifyMessage::new(EventKind::WorkStarted, &notification);
+        assert_eq!(start_at, message.finished_at);
+
+        let message = NotifyMessage::new(EventKind::WorkDone, &notification);
+        assert_eq!(3, message.id);
+        assert_eq!("review PR", message.description);
+        assert_eq!(1500, message.work_time);
+        assert_eq!(Utc.ymd(2023, 3, 1).and_hms(9, 25, 0), message.finished_at);
+
+        let message = NotifyMessage::new(EventKind::BreakDone, &notification);
+        assert_eq!(Utc.ymd(2023, 3, 1).and_hms(9, 30, 0), message.finished_at);
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let mut registry = NotifierRegistry::default();
+        registry.register(MockNotifier { ok: false });
+        registry.register(MockNotifier { ok: true });
+
+        let notification = Notification::new(1, "", 25 * 60, 5 * 60, Utc::now());
+        let report = registry
+            .notify(NotifyMessage::new(EventKind::WorkDone, &notification))
+            .await;
+
+        assert_eq!(
+            json!([
+                { "channel": "Fail", "ok": false, "reason": "configuration is empty" },
+                { "channel": "Ok", "ok": true, "reason": null },
+            ]),
+            serde_json::to_value(report).unwrap()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::{Configuration, NTFY_URL};
+use crate::error::{NotificationError, NotifyResult};
+
+/// NtfyNotifier publishes the notification to the topic of ntfy
+#[derive(Default)]
+pub struct NtfyNotifier {
+    server_url: String,
+    topic: Option<String>,
+    token: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    priority: Option<u8>,
+    tags: Vec<String>,
+}
+
+impl NtfyNotifier {
+    /// from_configuration returns None unless the topic or the server url is set.
+    /// A notifier without the topic is kept so that notify reports it.
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        let topic = configuration.get_ntfy_topic();
+        let server_url = configuration.get_ntfy_server_url();
+
+        if topic.is_none() && server_url == NTFY_URL {
+            return None;
+        }
+
+        Some(NtfyNotifier {
+            server_url: server_url.to_string(),
+            topic: topic.clone(),
+            token: configuration.get_ntfy_token().clone(),
+            username: configuration.get_ntfy_username().clone(),
+            password: configuration.get_ntfy_password().clone(),
+            priority: configuration.get_ntfy_priority(),
+            tags: configuration.get_ntfy_tags().to_vec(),
+        })
+    }
+
+    /// get_body returns the json message published to the root of the server
+    fn get_body(&self, topic: &str, message: &NotifyMessage) -> String {
+        let mut body = json!({
+            "topic": topic,
+            "title": message.summary,
+            "message": message.text
+        });
+        if let Some(priority) = self.priority {
+            body["priority"] = json!(priority);
+        }
+        if !self.tags.is_empty() {
+            body["tags"] = json!(self.tags);
+        }
+
+        body.to_string()
+    }
+}
+
+#[async_trait]
+impl Notifier for NtfyNotifier {
+    fn name(&self) -> String {
+        String::from("Ntfy")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let topic = match &self.topic {
+            Some(topic) => topic,
+            None => {
+                debug!("topic is none");
+                return Err(NotificationError::EmptyConfiguration);
+            }
+        };
+
+        let client = get_http_client();
+        let mut request = client
+            .post(&self.server_url)
+            .header("Content-Type", "application/json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        } else if let Some(username) = &self.username {
+            request = request.basic_auth(username, self.password.as_ref());
+        }
+
+        let resp = request
+            .body(self.get_body(topic, message))
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+
+        debug!("resp: {:?}", resp);
+
+        resp.map(|_| ()).map_err(NotificationError::Ntfy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    use super::NtfyNotifier;
+    use crate::error::NotificationError;
+    use crate::notification::notifier::stub::{get_message, spawn_stub_server};
+    use crate::notification::notifier::Notifier;
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, rx) = spawn_stub_server(200, r#"{"id": "message-id"}"#);
+
+        let notifier = NtfyNotifier {
+            server_url: url,
+            topic: Some(String::from("pomodoro")),
+            token: Some(String::from("tk_secret")),
+            priority: Some(4),
+            tags: vec![String::from("tomato")],
+            ..Default::default()
+        };
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("/", request.path);
+        assert_eq!(
+            Some("Bearer tk_secret"),
+            request.get_header("authorization")
+        );
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            json!({
+                "topic": "pomodoro",
+                "title": "Break time done!",
+                "message": "Break time finished. Now back to work!",
+                "priority": 4,
+                "tags": ["tomato"]
+            }),
+            body
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_basic_auth() {
+        let (url, rx) = spawn_stub_server(200, r#"{"id": "message-id"}"#);
+
+        let notifier = NtfyNotifier {
+            server_url: url,
+            topic: Some(String::from("pomodoro")),
+            username: Some(String::from("user")),
+            password: Some(String::from("pass")),
+            ..Default::default()
+        };
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            Some("Basic dXNlcjpwYXNz"),
+            request.get_header("authorization")
+        );
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(None, body.get("priority"));
+        assert_eq!(None, body.get("tags"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_error() {
+        let (url, _rx) = spawn_stub_server(403, r#"{"code": 40301}"#);
+
+        let notifier = NtfyNotifier {
+            server_url: url,
+            topic: Some(String::from("pomodoro")),
+            ..Default::default()
+        };
+        assert!(matches!(
+            notifier.notify(&get_message()).await,
+            Err(NotificationError::Ntfy(_))
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::{Configuration, SLACK_API_URL};
+use crate::error::{NotificationError, NotifyResult};
+
+/// SlackNotifier sends notification to slack
+pub struct SlackNotifier {
+    token: Option<String>,
+    channel: Option<String>,
+}
+
+impl SlackNotifier {
+    /// from_configuration returns the notifier if slack is specified in the configuration
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        let token = configuration.get_slack_token();
+        let channel = configuration.get_slack_channel();
+
+        if token.is_none() && channel.is_none() {
+            return None;
+        }
+
+        Some(SlackNotifier {
+            token: token.clone(),
+            channel: channel.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> String {
+        String::from("Slack")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let (token, channel) = match (&self.token, &self.channel) {
+            (Some(token), Some(channel)) => (token, channel),
+            _ => {
+                debug!("token or channel is none");
+                return Err(NotificationError::EmptyConfiguration);
+            }
+        };
+
+        let body = json!({
+            "channel": channel,
+            "text": message.text
+        })
+        .to_string();
+
+        let client = get_http_client();
+        let resp = client
+            .post(SLACK_API_URL)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(body)
+            .send()
+            .await;
+
+        debug!("resp: {:?}", resp);
+
+        resp.map(|_| ()).map_err(NotificationError::Slack)
+    }
+}
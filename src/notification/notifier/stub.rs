@@ -0,0 +1,104 @@
+//! A local http server for testing the notifiers, which records the requests
+use chrono::Utc;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use super::NotifyMessage;
+use crate::event::EventKind;
+use crate::notification::Notification;
+
+#[derive(Debug)]
+pub struct StubRequest {
+    pub method: String,
+    pub path: String,
+    // header names are in lowercase
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl StubRequest {
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == &name.to_lowercase())
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// get_message returns the break done message of the notification 2 sent by the notifiers
+pub fn get_message() -> NotifyMessage {
+    let notification = Notification::new(2, "", 25 * 60, 5 * 60, Utc::now());
+
+    NotifyMessage::new(EventKind::BreakDone, &notification).with_contents(
+        "Break time done!",
+        String::new(),
+        String::from("Break time finished. Now back to work!"),
+    )
+}
+
+/// spawn_stub_server responds to every request with the given status and body.
+/// It returns the url of the server and the receiver of the requests.
+pub fn spawn_stub_server(status: u16, response: &'static str) -> (String, Receiver<StubRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                match line.trim_end().split_once(':') {
+                    Some((key, value)) => {
+                        headers.push((key.trim().to_lowercase(), value.trim().to_string()))
+                    }
+                    None => break,
+                }
+            }
+
+            let length = headers
+                .iter()
+                .find(|(key, _)| key == "content-length")
+                .and_then(|(_, value)| value.parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            write!(
+                stream,
+                "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+
+            let request = StubRequest {
+                method,
+                path,
+                headers,
+                body: String::from_utf8_lossy(&body).to_string(),
+            };
+            if tx.send(request).is_err() {
+                break;
+            }
+        }
+    });
+
+    (url, rx)
+}
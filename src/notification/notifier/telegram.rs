@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::configuration::Configuration;
+use crate::error::{NotificationError, NotifyResult};
+
+/// TelegramNotifier sends notification to telegram using the sendMessage api of the bot
+pub struct TelegramNotifier {
+    bot_token: Option<String>,
+    chat_id: Option<String>,
+    api_url: String,
+}
+
+impl TelegramNotifier {
+    /// from_configuration returns None unless the bot token or the chat id is set.
+    /// A half configured notifier is kept so that notify reports the missing value.
+    pub fn from_configuration(configuration: &Configuration) -> Option<Self> {
+        let bot_token = configuration.get_telegram_bot_token();
+        let chat_id = configuration.get_telegram_chat_id();
+
+        if bot_token.is_none() && chat_id.is_none() {
+            return None;
+        }
+
+        Some(TelegramNotifier {
+            bot_token: bot_token.clone(),
+            chat_id,
+            api_url: configuration
+                .get_telegram_api_url()
+                .trim_end_matches('/')
+                .to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> String {
+        String::from("Telegram")
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let (bot_token, chat_id) = match (&self.bot_token, &self.chat_id) {
+            (Some(bot_token), Some(chat_id)) => (bot_token, chat_id),
+            _ => {
+                debug!("bot token or chat id is none");
+                return Err(NotificationError::EmptyConfiguration);
+            }
+        };
+
+        let body = json!({
+            "chat_id": chat_id,
+            "text": message.text
+        })
+        .to_string();
+
+        let client = get_http_client();
+        let resp = client
+            .post(format!("{}/bot{}/sendMessage", self.api_url, bot_token))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+
+        // the url contains the bot token, so it is not logged
+        debug!(
+            "resp: {:?}",
+            resp.as_ref()
+                .map(|resp| resp.status())
+                .map_err(|e| e.status())
+        );
+
+        resp.map(|_| ())
+            .map_err(|e| NotificationError::Telegram(e.without_url()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    use super::TelegramNotifier;
+    use crate::error::NotificationError;
+    use crate::notification::notifier::stub::{get_message, spawn_stub_server};
+    use crate::notification::notifier::Notifier;
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, rx) = spawn_stub_server(200, r#"{"ok": true, "result": {}}"#);
+
+        let notifier = TelegramNotifier {
+            bot_token: Some(String::from("123:token")),
+            chat_id: Some(String::from("-1001234567890")),
+            api_url: url,
+        };
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("/bot123:token/sendMessage", request.path);
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            json!({
+                "chat_id": "-1001234567890",
+                "text": "Break time finished. Now back to work!"
+            }),
+            body
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_error() {
+        let (url, _rx) = spawn_stub_server(401, r#"{"ok": false, "error_code": 401}"#);
+
+        let notifier = TelegramNotifier {
+            bot_token: Some(String::from("123:token")),
+            chat_id: Some(String::from("@pomodoro")),
+            api_url: url,
+        };
+        let err = notifier.notify(&get_message()).await.unwrap_err();
+        match err {
+            NotificationError::Telegram(e) => assert!(e.url().is_none()),
+            _ => panic!("unexpected error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use async_trait::async_trait;
+use reqwest::{Method, Url};
+use serde_json::{json, Value};
+
+use super::{get_http_client, Notifier, NotifyMessage};
+use crate::command::util;
+use crate::configuration::{Configuration, WebhookConfiguration};
+use crate::error::{NotificationError, NotifyResult};
+
+/// WebhookNotifier sends the notification to the url of the webhook
+pub struct WebhookNotifier {
+    webhook: WebhookConfiguration,
+}
+
+impl WebhookNotifier {
+    /// from_configuration returns the notifiers of the webhooks specified in the configuration
+    pub fn from_configuration(configuration: &Configuration) -> Vec<Self> {
+        configuration
+            .get_webhooks()
+            .iter()
+            .map(|webhook| WebhookNotifier {
+                webhook: webhook.clone(),
+            })
+            .collect()
+    }
+
+    /// check returns the error of the url or the method of the webhook.
+    /// The url is not in the error since it may contain the secret.
+    pub fn check(&self) -> Result<(), NotificationError> {
+        Url::parse(&self.webhook.url)
+            .map_err(|e| NotificationError::InvalidWebhook(format!("invalid url: {}", e)))?;
+
+        self.get_method().map(|_| ())
+    }
+
+    fn get_method(&self) -> Result<Method, NotificationError> {
+        match &self.webhook.method {
+            Some(method) => Method::from_bytes(method.to_uppercase().as_bytes()).map_err(|_| {
+                NotificationError::InvalidWebhook(format!("invalid method: {}", method))
+            }),
+            None => Ok(Method::POST),
+        }
+    }
+
+    /// get_body returns the body of the request. Without the template,
+    /// the body is a json object of all values.
+    fn get_body(&self, message: &NotifyMessage) -> String {
+        match &self.webhook.body {
+            Some(Value::String(template)) => render_template(template, message),
+            Some(template) => render_template(&template.to_string(), message),
+            None => json!({
+                "event": message.event.as_str(),
+                "id": message.id,
+                "description": message.description,
+                "work_time": message.work_time,
+                "break_time": message.break_time,
+                "finished_at": util::to_rfc3339(message.finished_at),
+                "message": message.text,
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// render_template replaces the placeholders like `{{description}}` with the values of the message.
+/// Strings are escaped so that they can be placed in json strings.
+/// Unknown placeholders are left as they are.
+fn render_template(template: &str, message: &NotifyMessage) -> String {
+    let mut rendered = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start..].find("}}") {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        rendered.push_str(&rest[..start]);
+        match get_placeholder_value(rest[start + 2..end].trim(), message) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+fn get_placeholder_value(name: &str, message: &NotifyMessage) -> Option<String> {
+    let value = match name {
+        "event" => message.event.as_str().to_string(),
+        "id" => message.id.to_string(),
+        "description" => escape_json_string(&message.description),
+        "work_time" => message.work_time.to_string(),
+        "break_time" => message.break_time.to_string(),
+        "finished_at" => util::to_rfc3339(message.finished_at),
+        "message" => escape_json_string(&message.text),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn escape_json_string(s: &str) -> String {
+    let quoted = Value::from(s).to_string();
+
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> String {
+        let host = Url::parse(&self.webhook.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from));
+
+        format!("Webhook ({})", host.as_deref().unwrap_or("invalid url"))
+    }
+
+    async fn notify(&self, message: &NotifyMessage) -> NotifyResult {
+        let method = self.get_method()?;
+
+        let client = get_http_client();
+        let mut request = client.request(method, &self.webhook.url);
+        if !self
+            .webhook
+            .headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case("content-type"))
+        {
+            request = request.header("Content-Type", "application/json");
+        }
+        for (key, value) in self.webhook.headers.iter() {
+            request = request.header(key, value);
+        }
+
+        let resp = request
+            .body(self.get_body(message))
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+
+        // the url may contain the secret, so it is not logged
+        debug!(
+            "resp: {:?}",
+            resp.as_ref()
+                .map(|resp| resp.status())
+                .map_err(|e| e.status())
+        );
+
+        resp.map(|_| ())
+            .map_err(|e| NotificationError::Webhook(e.without_url()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::{render_template, WebhookNotifier};
+    use crate::configuration::WebhookConfiguration;
+    use crate::error::NotificationError;
+    use crate::event::EventKind;
+    use crate::notification::notifier::stub::spawn_stub_server;
+    use crate::notification::notifier::{Notifier, NotifyMessage};
+    use crate::notification::Notification;
+
+    fn get_message() -> NotifyMessage {
+        let start_at = Utc.ymd(2023, 3, 1).and_hms(9, 0, 0);
+        let notification = Notification::new(3, "review \"PR\"", 25 * 60, 5 * 60, start_at);
+
+        NotifyMessage::new(EventKind::WorkDone, &notification).with_contents(
+            "Work time done!",
+            String::from("Work time finished.\nNow take a rest!"),
+            String::from("Work on 'review \"PR\"' done. Take a rest!"),
+        )
+    }
+
+    #[test]
+    fn test_render_template() {
+        let message = get_message();
+
+        let rendered = render_template(
+            r#"{"text": "{{event}} {{ id }}: {{description}}", "work": {{work_time}}, "at": "{{finished_at}}", "unknown": "{{unknown}}"}"#,
+            &message,
+        );
+        let json: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(
+            json!({
+                "text": "work_done 3: review \"PR\"",
+                "work": 1500,
+                "at": "2023-03-01T09:25:00Z",
+                "unknown": "{{unknown}}",
+            }),
+            json
+        );
+
+        assert_eq!("{{id", render_template("{{id", &message));
+    }
+
+    #[test]
+    fn test_check() {
+        let webhook = WebhookConfiguration {
+            url: String::from("https://chat.example.com/hooks/secret"),
+            method: Some(String::from("patch")),
+            headers: BTreeMap::new(),
+            body: None,
+        };
+        assert!(WebhookNotifier {
+            webhook: webhook.clone()
+        }
+        .check()
+        .is_ok());
+
+        let notifier = WebhookNotifier {
+            webhook: WebhookConfiguration {
+                url: String::from("chat.example.com/hooks/secret"),
+                ..webhook.clone()
+            },
+        };
+        assert_eq!("Webhook (invalid url)", notifier.name());
+        let err = notifier.check().unwrap_err().to_string();
+        assert!(err.starts_with("invalid url"));
+        assert!(!err.contains("secret"));
+
+        let notifier = WebhookNotifier {
+            webhook: WebhookConfiguration {
+                method: Some(String::from("NOT A METHOD")),
+                ..webhook
+            },
+        };
+        assert_eq!(
+            "invalid method: NOT A METHOD",
+            notifier.check().unwrap_err().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, rx) = spawn_stub_server(200, "{}");
+
+        let mut headers = BTreeMap::new();
+        headers.insert(String::from("X-Token"), String::from("secret"));
+        let notifier = WebhookNotifier {
+            webhook: WebhookConfiguration {
+                url: format!("{}/hooks/pomodoro", url),
+                method: Some(String::from("put")),
+                headers,
+                body: Some(json!({ "text": "{{event}}: {{description}}" })),
+            },
+        };
+        assert_eq!("Webhook (127.0.0.1)", notifier.name());
+
+        notifier.notify(&get_message()).await.unwrap();
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("PUT", request.method);
+        assert_eq!("/hooks/pomodoro", request.path);
+        assert_eq!(Some("secret"), request.get_header("x-token"));
+        assert_eq!(Some("application/json"), request.get_header("content-type"));
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(json!({ "text": "work_done: review \"PR\"" }), body);
+    }
+
+    #[tokio::test]
+    async fn test_notify_default_body_and_error() {
+        let (url, rx) = spawn_stub_server(500, "{}");
+
+        let notifier = WebhookNotifier {
+            webhook: WebhookConfiguration {
+                url,
+                method: None,
+                headers: BTreeMap::new(),
+                body: None,
+            },
+        };
+        match notifier.notify(&get_message()).await {
+            Err(NotificationError::Webhook(e)) => assert!(e.url().is_none()),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!("POST", request.method);
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            json!({
+                "event": "work_done",
+                "id": 3,
+                "description": "review \"PR\"",
+                "work_time": 1500,
+                "break_time": 300,
+                "finished_at": "2023-03-01T09:25:00Z",
+                "message": "Work on 'review \"PR\"' done. Take a rest!",
+            }),
+            body
+        );
+
+        let notifier = WebhookNotifier {
+            webhook: WebhookConfiguration {
+                url: String::from("http://127.0.0.1:1"),
+                method: Some(String::from("NOT A METHOD")),
+                headers: BTreeMap::new(),
+                body: None,
+            },
+        };
+        assert_eq!(
+            "invalid method: NOT A METHOD",
+            notifier
+                .notify(&get_message())
+                .await
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
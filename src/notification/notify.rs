@@ -1,19 +1,16 @@
-#[cfg(target_os = "linux")]
-use notify_rust::Hint;
-use notify_rust::{Notification as NR_Notification, Timeout as NR_Timeout};
-use serde_json::json;
-use tokio::join;
-
 #[cfg(target_os = "macos")]
 use std::process::Command;
 use std::sync::Arc;
 
-use crate::configuration::{Configuration, SLACK_API_URL};
-use crate::error::{NotificationError, NotifyResult};
-use crate::report;
+use super::notifier::{NotifierRegistry, NotifyMessage};
+use super::Notification;
+use crate::configuration::Configuration;
+use crate::error::NotificationError;
+use crate::event::EventKind;
+use crate::report::NotifyReport;
 
 #[cfg(target_os = "macos")]
-fn notify_terminal_notifier(message: &'static str) {
+fn notify_terminal_notifier(message: &str) {
     use std::io::ErrorKind;
 
     let result = Command::new("terminal-notifier")
@@ -33,121 +30,143 @@ fn notify_terminal_notifier(message: &'static str) {
     }
 }
 
-/// notify_slack send notification to slack
-/// it uses slack notification if configuration specified
-async fn notify_slack(message: &'static str, configuration: &Arc<Configuration>) -> NotifyResult {
-    let token = configuration.get_slack_token();
-    let channel = configuration.get_slack_channel();
-
-    if token.is_none() || channel.is_none() {
-        debug!("token or channel is none");
-        return Err(NotificationError::EmptyConfiguration);
+/// get_start_message returns the session started message including the description if present
+fn get_start_message(description: &str) -> String {
+    if description.is_empty() {
+        String::from("work started. Stay focused!")
+    } else {
+        format!("Work on '{}' started. Stay focused!", description)
     }
+}
 
-    let body = json!({
-        "channel": channel,
-        "text": message
-    })
-    .to_string();
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(SLACK_API_URL)
-        .header("Content-Type", "application/json")
-        .header(
-            "Authorization",
-            format!("Bearer {}", token.clone().unwrap()),
-        )
-        .body(body)
-        .send()
-        .await;
-
-    debug!("resp: {:?}", resp);
+/// get_work_message returns the work done message including the description if present
+fn get_work_message(description: &str) -> String {
+    if description.is_empty() {
+        String::from("work done. Take a rest!")
+    } else {
+        format!("Work on '{}' done. Take a rest!", description)
+    }
+}
 
-    resp.map(|_| ()).map_err(NotificationError::Slack)
+/// get_break_message returns the break done message including the description if present
+fn get_break_message(description: &str) -> String {
+    if description.is_empty() {
+        String::from("break done. Get back to work")
+    } else {
+        format!("Break after '{}' done. Get back to work", description)
+    }
 }
 
-/// notify_discord send notification to discord
-/// use discord webhook notification if configuration specified
-async fn notify_discord(message: &'static str, configuration: &Arc<Configuration>) -> NotifyResult {
-    let webhook_url = match configuration.get_discord_webhook_url() {
-        Some(url) => url,
-        None => {
-            debug!("webhook_url is none");
-            return Err(NotificationError::EmptyConfiguration);
-        }
-    };
+pub async fn notify_start(
+    configuration: &Arc<Configuration>,
+    notification: &Notification,
+) -> Result<NotifyReport, NotificationError> {
+    let description = notification.get_description();
+    let message = get_start_message(description);
 
-    let body = json!({ "content": message }).to_string();
+    #[cfg(target_os = "macos")]
+    notify_terminal_notifier(&message);
 
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(webhook_url)
-        .header("Content-Type", "application/json")
-        .body(body)
-        .send()
+    let desktop_body = if description.is_empty() {
+        String::from("Work time started.\nStay focused!")
+    } else {
+        format!("Work on '{}' started.\nStay focused!", description)
+    };
+    let report = NotifierRegistry::new(configuration)
+        .notify(
+            NotifyMessage::new(EventKind::WorkStarted, notification).with_contents(
+                "Session started!",
+                desktop_body,
+                message,
+            ),
+        )
         .await;
 
-    debug!("resp: {:?}", resp);
-
-    resp.map(|_| ()).map_err(NotificationError::Discord)
+    Ok(report)
 }
 
-/// notify_desktop send notification to desktop.
-/// use notify-rust library for desktop notification
-async fn notify_desktop(summary_message: &'static str, body_message: &'static str) -> NotifyResult {
-    let mut notification = NR_Notification::new();
-    let notification = notification
-        .summary(summary_message)
-        .body(body_message)
-        .appname("pomodoro")
-        .timeout(NR_Timeout::Milliseconds(5000));
-
-    #[cfg(target_os = "linux")]
-    notification
-        .hint(Hint::Category("im.received".to_owned()))
-        .sound_name("message-new-instant");
-
-    notification
-        .show()
-        .map(|_| ())
-        .map_err(NotificationError::Desktop)
-}
+pub async fn notify_work(
+    configuration: &Arc<Configuration>,
+    notification: &Notification,
+) -> Result<NotifyReport, NotificationError> {
+    let description = notification.get_description();
+    let message = get_work_message(description);
 
-pub async fn notify_work(configuration: &Arc<Configuration>) -> Result<String, NotificationError> {
     // TODO(young): Handle this also as async later
     #[cfg(target_os = "macos")]
-    notify_terminal_notifier("work done. Take a rest!");
-
-    let desktop_fut = notify_desktop("Work time done!", "Work time finished.\nNow take a rest!");
-    let slack_fut = notify_slack("work done. Take a rest!", configuration);
-    let discord_fut = notify_discord("work done. Take a rest!", configuration);
+    notify_terminal_notifier(&message);
 
-    let (desktop_result, slack_result, discord_result) = join!(desktop_fut, slack_fut, discord_fut);
+    let desktop_body = if description.is_empty() {
+        String::from("Work time finished.\nNow take a rest!")
+    } else {
+        format!("Work on '{}' finished.\nNow take a rest!", description)
+    };
+    let report = NotifierRegistry::new(configuration)
+        .notify(
+            NotifyMessage::new(EventKind::WorkDone, notification).with_contents(
+                "Work time done!",
+                desktop_body,
+                message,
+            ),
+        )
+        .await;
 
-    Ok(report::generate_notify_report(
-        desktop_result,
-        slack_result,
-        discord_result,
-    ))
+    Ok(report)
 }
 
-pub async fn notify_break(configuration: &Arc<Configuration>) -> Result<String, NotificationError> {
+pub async fn notify_break(
+    configuration: &Arc<Configuration>,
+    notification: &Notification,
+) -> Result<NotifyReport, NotificationError> {
+    let description = notification.get_description();
+    let message = get_break_message(description);
+
     #[cfg(target_os = "macos")]
-    notify_terminal_notifier("break done. Get back to work");
-
-    let desktop_fut = notify_desktop(
-        "Break time done!",
-        "Break time finished.\n Now back to work!",
-    );
-    let slack_fut = notify_slack("break done. Get back to work", configuration);
-    let discord_fut = notify_discord("break done. Get back to work", configuration);
-
-    let (desktop_result, slack_result, discord_result) = join!(desktop_fut, slack_fut, discord_fut);
-
-    Ok(report::generate_notify_report(
-        desktop_result,
-        slack_result,
-        discord_result,
-    ))
+    notify_terminal_notifier(&message);
+
+    let desktop_body = if description.is_empty() {
+        String::from("Break time finished.\n Now back to work!")
+    } else {
+        format!(
+            "Break after '{}' finished.\n Now back to work!",
+            description
+        )
+    };
+    let report = NotifierRegistry::new(configuration)
+        .notify(
+            NotifyMessage::new(EventKind::BreakDone, notification).with_contents(
+                "Break time done!",
+                desktop_body,
+                message,
+            ),
+        )
+        .await;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_break_message, get_start_message, get_work_message};
+
+    #[test]
+    fn test_get_messages() {
+        assert_eq!("work started. Stay focused!", get_start_message(""));
+        assert_eq!(
+            "Work on 'review PR #42' started. Stay focused!",
+            get_start_message("review PR #42")
+        );
+
+        assert_eq!("work done. Take a rest!", get_work_message(""));
+        assert_eq!(
+            "Work on 'review PR #42' done. Take a rest!",
+            get_work_message("review PR #42")
+        );
+
+        assert_eq!("break done. Get back to work", get_break_message(""));
+        assert_eq!(
+            "Break after 'review PR #42' done. Get back to work",
+            get_break_message("review PR #42")
+        );
+    }
 }
@@ -1,8 +1,12 @@
 use colored::{ColoredString, Colorize};
+use serde::Serialize;
+use std::fmt;
 use tabled::{Style, Table, Tabled};
 
+use crate::configuration::StorageType;
 use crate::error::ConfigurationError;
 use crate::error::NotifyResult;
+use crate::notification::notifier::{Notifier, NotifierRegistry, WebhookNotifier};
 use crate::Configuration;
 
 #[derive(Tabled)]
@@ -13,7 +17,7 @@ struct Report {
 }
 
 impl Report {
-    pub fn new(ok: &'static str, desc: &'static str) -> Self {
+    pub fn new(ok: &'static str, desc: &str) -> Self {
         Report {
             ok: ok.green(),
             desc: String::from(desc),
@@ -23,14 +27,63 @@ impl Report {
 
     // TODO(young): e should be string or ConfigurationError?
     pub fn update_reason(mut self, e: &dyn std::error::Error) -> Self {
-        let mut vec = vec![format!("{}", e)];
-        if let Some(s) = e.source() {
-            vec.push(s.to_string());
+        self.reason = get_reason(e).red();
+
+        self
+    }
+}
+
+/// get_reason returns the error message followed by its source if present
+fn get_reason(e: &dyn std::error::Error) -> String {
+    let mut vec = vec![format!("{}", e)];
+    if let Some(s) = e.source() {
+        vec.push(s.to_string());
+    }
+
+    vec.join("\n")
+}
+
+/// The result of the notification sent to a channel
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ChannelResult {
+    channel: String,
+    ok: bool,
+    reason: Option<String>,
+}
+
+impl ChannelResult {
+    fn new(channel: String, result: NotifyResult) -> Self {
+        ChannelResult {
+            channel,
+            ok: result.is_ok(),
+            reason: result.err().map(|e| get_reason(&e)),
         }
+    }
+}
 
-        self.reason = vec.join("\n").red();
+/// NotifyReport is shown as a table, and serialized as the list of the results
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct NotifyReport {
+    results: Vec<ChannelResult>,
+}
 
-        self
+impl fmt::Display for NotifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.results.iter().map(|r| match r.reason {
+            Some(ref reason) => Report {
+                ok: "X".green(),
+                desc: r.channel.clone(),
+                reason: reason.as_str().red(),
+            },
+            None => Report {
+                ok: "O".green(),
+                desc: r.channel.clone(),
+                reason: ColoredString::default(),
+            },
+        });
+
+        write!(f, "{}", Table::new(rows).with(Style::modern()))
     }
 }
 
@@ -62,6 +115,53 @@ pub fn generate_configuration_report(
             .update_reason(&ConfigurationError::DiscordConfigNotFound),
     };
 
+    let telegram_message = match (
+        config.get_telegram_bot_token(),
+        config.get_telegram_chat_id(),
+    ) {
+        (Some(_), Some(_)) => Report::new("O", "telegram"),
+        _ => {
+            Report::new("X", "telegram").update_reason(&ConfigurationError::TelegramConfigNotFound)
+        }
+    };
+
+    let matrix_message = match (
+        config.get_matrix_homeserver_url(),
+        config.get_matrix_access_token(),
+        config.get_matrix_room_id(),
+    ) {
+        (Some(_), Some(_), Some(_)) => Report::new("O", "matrix"),
+        _ => Report::new("X", "matrix").update_reason(&ConfigurationError::MatrixConfigNotFound),
+    };
+
+    let ntfy_message = match config.get_ntfy_topic() {
+        Some(_) => Report::new("O", "ntfy"),
+        None => Report::new("X", "ntfy").update_reason(&ConfigurationError::NtfyConfigNotFound),
+    };
+
+    let gotify_message = match (
+        config.get_gotify_server_url(),
+        config.get_gotify_app_token(),
+    ) {
+        (Some(_), Some(_)) => Report::new("O", "gotify"),
+        _ => Report::new("X", "gotify").update_reason(&ConfigurationError::GotifyConfigNotFound),
+    };
+
+    let webhook_messages =
+        WebhookNotifier::from_configuration(config)
+            .into_iter()
+            .map(|notifier| match notifier.check() {
+                Ok(_) => Report::new("O", &notifier.name()),
+                Err(e) => Report::new("X", &notifier.name()).update_reason(&e),
+            });
+
+    let storage_message = match (config.get_storage_type(), config.get_storage_path()) {
+        (StorageType::Memory, _) => Report::new("O", "storage (memory)"),
+        (StorageType::Sled, Some(_)) => Report::new("O", "storage (sled)"),
+        (StorageType::Sled, None) => Report::new("X", "storage (sled)")
+            .update_reason(&ConfigurationError::StoragePathNotFound),
+    };
+
     let work_time_default_value_message = match config.get_work_time() {
         Some(_) => Report::new("O", "default_work_time"),
         None => Report::new("X", "default_work_time")
@@ -74,39 +174,66 @@ pub fn generate_configuration_report(
             .update_reason(&ConfigurationError::UnspecifiedBreakTime),
     };
 
-    Table::new(vec![
+    let long_break_time_default_value_message = match config.get_long_break_time() {
+        Some(_) => Report::new("O", "default_long_break_time"),
+        None => Report::new("X", "default_long_break_time")
+            .update_reason(&ConfigurationError::UnspecifiedLongBreakTime),
+    };
+
+    let long_break_interval_message = match config.get_long_break_interval() {
+        Some(_) => Report::new("O", "long_break_interval"),
+        None => Report::new("X", "long_break_interval")
+            .update_reason(&ConfigurationError::UnspecifiedLongBreakInterval),
+    };
+
+    let timezone_message = match (config.get_timezone_name(), config.get_timezone()) {
+        (Some(_), Some(_)) => Report::new("O", "timezone"),
+        (Some(name), None) => Report::new("X", "timezone")
+            .update_reason(&ConfigurationError::InvalidTimezone(name.clone())),
+        (None, _) => {
+            Report::new("X", "timezone").update_reason(&ConfigurationError::UnspecifiedTimezone)
+        }
+    };
+
+    let notifiers_message = Report {
+        ok: "O".green(),
+        desc: format!(
+            "notifiers ({})",
+            NotifierRegistry::new(config).get_names().join(", ")
+        ),
+        reason: ColoredString::default(),
+    };
+
+    let mut rows = vec![
         config_err_message,
         slack_channel_message,
         slack_token_message,
         discord_webhook_url_message,
+        telegram_message,
+        matrix_message,
+        ntfy_message,
+        gotify_message,
+    ];
+    rows.extend(webhook_messages);
+    rows.extend(vec![
+        notifiers_message,
+        storage_message,
         work_time_default_value_message,
         break_time_default_value_message,
-    ])
-    .with(Style::modern())
-    .to_string()
-}
-
-pub fn generate_notify_report(
-    desktop: NotifyResult,
-    slack: NotifyResult,
-    discord: NotifyResult,
-) -> String {
-    let desktop_message = match desktop {
-        Ok(_) => Report::new("O", "Desktop"),
-        Err(e) => Report::new("X", "Desktop").update_reason(&e),
-    };
+        long_break_time_default_value_message,
+        long_break_interval_message,
+        timezone_message,
+    ]);
 
-    let slack_message = match slack {
-        Ok(_) => Report::new("O", "Slack"),
-        Err(e) => Report::new("X", "Slack").update_reason(&e),
-    };
-
-    let discord_message = match discord {
-        Ok(_) => Report::new("O", "Discord"),
-        Err(e) => Report::new("X", "Discord").update_reason(&e),
-    };
+    Table::new(rows).with(Style::modern()).to_string()
+}
 
-    Table::new(vec![desktop_message, slack_message, discord_message])
-        .with(Style::modern())
-        .to_string()
+/// generate_notify_report reports the results of the channels in the given order
+pub fn generate_notify_report(results: Vec<(String, NotifyResult)>) -> NotifyReport {
+    NotifyReport {
+        results: results
+            .into_iter()
+            .map(|(channel, result)| ChannelResult::new(channel, result))
+            .collect(),
+    }
 }
@@ -0,0 +1,539 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use tabled::Tabled;
+
+use crate::command::util;
+use crate::notification::ArchivedNotification;
+
+pub const DEFAULT_STATS_DAYS: u16 = 7;
+const HEATMAP_WEEKS: i64 = 53;
+// the colors of the heatmap levels, from none to the most focused
+const HEATMAP_COLORS: [(u8, u8, u8); 5] = [
+    (235, 237, 240),
+    (155, 233, 168),
+    (64, 196, 99),
+    (48, 161, 78),
+    (33, 110, 57),
+];
+// used when colors are off
+const HEATMAP_PLAIN_CELLS: [char; 5] = ['.', '-', '+', '*', '#'];
+
+/// The number of sessions and the focused seconds of them
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Total {
+    sessions: u32,
+    focused: u32,
+}
+
+impl Total {
+    fn add(&mut self, focused: u32) {
+        self.sessions += 1;
+        self.focused = self.focused.saturating_add(focused);
+    }
+}
+
+/// Statistics aggregates the work of the archived notifications.
+/// Days are split in the given timezone, and a week starts on Monday.
+#[derive(Debug)]
+pub struct Statistics {
+    today: Total,
+    this_week: Total,
+    this_month: Total,
+    // the last few days including today, the oldest first
+    daily: Vec<(NaiveDate, Total)>,
+    average_session: u32,
+    completed: u32,
+    cut_short: u32,
+    current_streak: u32,
+    longest_streak: u32,
+}
+
+#[derive(Tabled)]
+pub struct TotalRow {
+    period: String,
+    sessions: u32,
+    focused: String,
+}
+
+#[derive(Tabled)]
+pub struct SummaryRow {
+    stat: &'static str,
+    value: String,
+}
+
+impl Statistics {
+    pub fn new(
+        notifications: &[ArchivedNotification],
+        now: DateTime<Utc>,
+        timezone: Option<Tz>,
+        days: u16,
+    ) -> Self {
+        let today = get_date(now, timezone);
+        let week_start = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+        let month_start = today.with_day(1).unwrap_or(today);
+
+        let by_date = get_totals_by_date(notifications, now, timezone);
+        let cut_short = notifications
+            .iter()
+            .filter(|n| is_session(n, now) && n.is_cut_short())
+            .count() as u32;
+        let completed = by_date.values().map(|t| t.sessions).sum::<u32>() - cut_short;
+
+        let sum_since = |since: NaiveDate| {
+            by_date
+                .range(since..=today)
+                .fold(Total::default(), |mut acc, (_, total)| {
+                    acc.sessions += total.sessions;
+                    acc.focused = acc.focused.saturating_add(total.focused);
+                    acc
+                })
+        };
+
+        let daily = (0..i64::from(days))
+            .rev()
+            .map(|i| today - Duration::days(i))
+            .map(|date| (date, by_date.get(&date).copied().unwrap_or_default()))
+            .collect();
+
+        let all = sum_since(NaiveDate::MIN);
+        let average_session = match all.sessions {
+            0 => 0,
+            sessions => all.focused / sessions,
+        };
+
+        let (current_streak, longest_streak) = get_streaks(by_date.keys().copied(), today);
+
+        Statistics {
+            today: by_date.get(&today).copied().unwrap_or_default(),
+            this_week: sum_since(week_start),
+            this_month: sum_since(month_start),
+            daily,
+            average_session,
+            completed,
+            cut_short,
+            current_streak,
+            longest_streak,
+        }
+    }
+
+    pub fn get_total_rows(&self) -> Vec<TotalRow> {
+        [
+            ("today", self.today),
+            ("this week", self.this_week),
+            ("this month", self.this_month),
+        ]
+        .into_iter()
+        .map(|(period, total)| TotalRow {
+            period: period.to_string(),
+            sessions: total.sessions,
+            focused: util::format_duration(total.focused),
+        })
+        .collect()
+    }
+
+    pub fn get_daily_rows(&self) -> Vec<TotalRow> {
+        self.daily
+            .iter()
+            .map(|(date, total)| TotalRow {
+                period: date.format("%F (%a)").to_string(),
+                sessions: total.sessions,
+                focused: util::format_duration(total.focused),
+            })
+            .collect()
+    }
+
+    /// to_json returns the statistics printed with `--output json`. Times are in seconds.
+    pub fn to_json(&self) -> Value {
+        let daily = self
+            .daily
+            .iter()
+            .map(|(date, total)| {
+                json!({
+                    "date": date.format("%F").to_string(),
+                    "sessions": total.sessions,
+                    "focused": total.focused,
+                })
+            })
+            .collect::<Vec<Value>>();
+
+        json!({
+            "today": self.today,
+            "this_week": self.this_week,
+            "this_month": self.this_month,
+            "daily": daily,
+            "average_session": self.average_session,
+            "completed": self.completed,
+            "cut_short": self.cut_short,
+            "current_streak": self.current_streak,
+            "longest_streak": self.longest_streak,
+        })
+    }
+
+    pub fn get_summary_rows(&self) -> Vec<SummaryRow> {
+        let days = |n: u32| format!("{} day{}", n, if n == 1 { "" } else { "s" });
+
+        vec![
+            SummaryRow {
+                stat: "average session",
+                value: util::format_duration(self.average_session),
+            },
+            SummaryRow {
+                stat: "completed",
+                value: self.completed.to_string(),
+            },
+            SummaryRow {
+                stat: "cut short",
+                value: self.cut_short.to_string(),
+            },
+            SummaryRow {
+                stat: "current streak",
+                value: days(self.current_streak),
+            },
+            SummaryRow {
+                stat: "longest streak",
+                value: days(self.longest_streak),
+            },
+        ]
+    }
+}
+
+/// Heatmap shows the focused time per day of the last year like the GitHub contributions.
+/// Each column is a week starting on Monday.
+#[derive(Debug)]
+pub struct Heatmap {
+    today: NaiveDate,
+    focused: BTreeMap<NaiveDate, u32>,
+}
+
+impl Heatmap {
+    pub fn new(
+        notifications: &[ArchivedNotification],
+        now: DateTime<Utc>,
+        timezone: Option<Tz>,
+    ) -> Self {
+        let focused = get_totals_by_date(notifications, now, timezone)
+            .into_iter()
+            .map(|(date, total)| (date, total.focused))
+            .collect();
+
+        Heatmap {
+            today: get_date(now, timezone),
+            focused,
+        }
+    }
+
+    fn get_first_date(&self) -> NaiveDate {
+        let monday =
+            self.today - Duration::days(i64::from(self.today.weekday().num_days_from_monday()));
+
+        monday - Duration::weeks(HEATMAP_WEEKS - 1)
+    }
+
+    /// to_json returns the focused seconds of the days shown in the heatmap, keyed by date.
+    pub fn to_json(&self) -> Value {
+        let focused = self
+            .focused
+            .range(self.get_first_date()..=self.today)
+            .map(|(date, focused)| (date.format("%F").to_string(), json!(focused)))
+            .collect::<Map<String, Value>>();
+
+        json!({
+            "from": self.get_first_date().format("%F").to_string(),
+            "to": self.today.format("%F").to_string(),
+            "focused": focused,
+        })
+    }
+
+    /// render returns the heatmap, which uses plain characters instead of colors if `use_color` is false.
+    pub fn render(&self, use_color: bool) -> String {
+        let first_date = self.get_first_date();
+        let total = self
+            .focused
+            .range(first_date..=self.today)
+            .fold(0u32, |acc, (_, focused)| acc.saturating_add(*focused));
+
+        let mut lines = vec![format!(
+            "{} focused in the last year",
+            util::format_duration(total)
+        )];
+
+        // the month label is put on the first week of the month, if there is enough space
+        let mut month_line = String::from("    ");
+        for week in 0..HEATMAP_WEEKS {
+            let monday = first_date + Duration::weeks(week);
+            let is_new_month = week == 0 || monday.day() <= 7;
+            let column = 4 + week as usize * 2;
+
+            if is_new_month && month_line.len() <= column {
+                month_line.push_str(&" ".repeat(column - month_line.len()));
+                month_line.push_str(&monday.format("%b").to_string());
+            }
+        }
+        lines.push(month_line);
+
+        for weekday in 0..7 {
+            let mut line = match weekday {
+                0 => String::from("Mon "),
+                2 => String::from("Wed "),
+                4 => String::from("Fri "),
+                _ => String::from("    "),
+            };
+
+            for week in 0..HEATMAP_WEEKS {
+                let date = first_date + Duration::weeks(week) + Duration::days(weekday);
+                if date > self.today {
+                    break;
+                }
+
+                let focused = self.focused.get(&date).copied().unwrap_or_default();
+                line.push_str(&get_heatmap_cell(get_heatmap_level(focused), use_color));
+                line.push(' ');
+            }
+            lines.push(line.trim_end().to_string());
+        }
+
+        let legend = (0..HEATMAP_COLORS.len())
+            .map(|level| get_heatmap_cell(level, use_color))
+            .collect::<Vec<String>>()
+            .join(" ");
+        lines.push(format!(
+            "    Less {} More (0m, <30m, <1h, <2h, 2h+)",
+            legend
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/// get_heatmap_level returns the level of the focused seconds, from 0 to 4.
+fn get_heatmap_level(focused: u32) -> usize {
+    match focused / 60 {
+        _ if focused == 0 => 0,
+        0..=29 => 1,
+        30..=59 => 2,
+        60..=119 => 3,
+        _ => 4,
+    }
+}
+
+fn get_heatmap_cell(level: usize, use_color: bool) -> String {
+    if use_color {
+        let (r, g, b) = HEATMAP_COLORS[level];
+        "■".truecolor(r, g, b).to_string()
+    } else {
+        HEATMAP_PLAIN_CELLS[level].to_string()
+    }
+}
+
+/// get_totals_by_date sums up the work of the sessions finished by the given time per day.
+fn get_totals_by_date(
+    notifications: &[ArchivedNotification],
+    now: DateTime<Utc>,
+    timezone: Option<Tz>,
+) -> BTreeMap<NaiveDate, Total> {
+    let mut by_date: BTreeMap<NaiveDate, Total> = BTreeMap::new();
+
+    for n in notifications.iter().filter(|n| is_session(n, now)) {
+        let date = get_date(n.get_start_at(), timezone);
+        by_date.entry(date).or_default().add(n.get_work_time());
+    }
+
+    by_date
+}
+
+/// is_session checks whether the work of the notification is finished by the given time.
+/// Break only notifications, e.g. snoozed ones, and the ones still running are not focus sessions.
+fn is_session(n: &ArchivedNotification, now: DateTime<Utc>) -> bool {
+    n.get_work_time() > 0 && n.get_work_expired_at() <= now
+}
+
+/// get_date returns the date of the time in the timezone, or in the local timezone if it's None.
+fn get_date(time: DateTime<Utc>, timezone: Option<Tz>) -> NaiveDate {
+    match timezone {
+        Some(tz) => time.with_timezone(&tz).date_naive(),
+        None => time.with_timezone(&Local).date_naive(),
+    }
+}
+
+/// get_streaks returns the current and the longest number of consecutive days focused.
+/// The current streak is still alive if nothing is done today yet but yesterday is focused.
+fn get_streaks<I>(dates: I, today: NaiveDate) -> (u32, u32)
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    let (mut current, mut longest) = (0, 0);
+    let mut last: Option<NaiveDate> = None;
+
+    // dates are sorted in ascending order
+    for date in dates.filter(|d| *d <= today) {
+        current = match last {
+            Some(last) if date - last == Duration::days(1) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        last = Some(date);
+    }
+
+    match last {
+        Some(last) if today - last <= Duration::days(1) => (current, longest),
+        _ => (0, longest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use chrono_tz::Tz;
+    use serde_json::json;
+
+    use super::{get_heatmap_level, get_streaks, Heatmap, Statistics};
+    use crate::notification::{ArchivedNotification, Notification};
+
+    fn archived(id: u16, start_at: chrono::DateTime<Utc>, work_time: u32) -> ArchivedNotification {
+        ArchivedNotification::from(Notification::new(id, "", work_time, 5 * 60, start_at))
+    }
+
+    #[test]
+    fn test_get_streaks() {
+        let today = NaiveDate::from_ymd(2026, 10, 17);
+        let day = |n: i64| today - Duration::days(n);
+
+        assert_eq!((0, 0), get_streaks(vec![].into_iter(), today));
+
+        // today is not focused yet
+        let dates = vec![day(6), day(5), day(4), day(2), day(1)];
+        assert_eq!((2, 3), get_streaks(dates.into_iter(), today));
+
+        let dates = vec![day(3), day(2), day(1), day(0)];
+        assert_eq!((4, 4), get_streaks(dates.into_iter(), today));
+
+        // broken streak
+        let dates = vec![day(4), day(3)];
+        assert_eq!((0, 2), get_streaks(dates.into_iter(), today));
+    }
+
+    #[test]
+    fn test_get_heatmap_level() {
+        assert_eq!(0, get_heatmap_level(0));
+        assert_eq!(1, get_heatmap_level(45));
+        assert_eq!(1, get_heatmap_level(29 * 60));
+        assert_eq!(2, get_heatmap_level(30 * 60));
+        assert_eq!(3, get_heatmap_level(90 * 60));
+        assert_eq!(4, get_heatmap_level(5 * 3600));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let now = Utc.ymd(2026, 10, 17).and_hms(12, 0, 0);
+        let notifications = vec![
+            archived(0, now - Duration::hours(3), 50 * 60),
+            archived(1, now - Duration::hours(2), 90 * 60),
+            archived(2, now - Duration::days(1), 25 * 60),
+            // out of the heatmap
+            archived(3, now - Duration::weeks(60), 25 * 60),
+        ];
+
+        let heatmap = Heatmap::new(&notifications, now, Some(Tz::UTC));
+        let rendered = heatmap.render(false);
+        let lines = rendered.lines().collect::<Vec<&str>>();
+
+        // title, months, 7 weekdays and legend
+        assert_eq!(10, lines.len());
+        assert_eq!("2h45m focused in the last year", lines[0]);
+        assert!(lines[1].contains("Oct") && lines[1].contains("Jan"));
+        assert!(lines[2].starts_with("Mon . . "));
+
+        // 2026-10-17 is Saturday, which is the last day
+        assert!(lines[7].ends_with(". #"), "{}", lines[7]);
+        assert!(lines[6].ends_with(". -"), "{}", lines[6]);
+        // sunday of this week is not yet
+        assert_eq!(lines[2].len(), lines[8].len() + 2);
+
+        assert_eq!(
+            "    Less . - + * # More (0m, <30m, <1h, <2h, 2h+)",
+            lines[9]
+        );
+
+        let json = heatmap.to_json();
+        assert_eq!(json!("2025-10-13"), json["from"]);
+        assert_eq!(json!("2026-10-17"), json["to"]);
+        assert_eq!(
+            json!({ "2026-10-16": 25 * 60, "2026-10-17": 140 * 60 }),
+            json["focused"]
+        );
+    }
+
+    #[test]
+    fn test_statistics() {
+        let tz: Tz = "Asia/Seoul".parse().unwrap();
+        // 2026-10-17 is Saturday
+        let now = tz.ymd(2026, 10, 17).and_hms(21, 0, 0).with_timezone(&Utc);
+
+        let mut cut_short = Notification::new(3, "", 25 * 60, 5 * 60, now - Duration::hours(2));
+        cut_short.skip(now - Duration::hours(2) + Duration::minutes(10));
+        // deleted before it started
+        let mut deleted = Notification::new(8, "", 25 * 60, 5 * 60, now + Duration::minutes(5));
+        deleted.cut_off(now);
+
+        let notifications = vec![
+            // the previous month, 2026-09-30 in Seoul
+            archived(
+                0,
+                tz.ymd(2026, 9, 30).and_hms(10, 0, 0).with_timezone(&Utc),
+                50 * 60,
+            ),
+            // Monday of this week
+            archived(
+                1,
+                tz.ymd(2026, 10, 12).and_hms(10, 0, 0).with_timezone(&Utc),
+                25 * 60,
+            ),
+            archived(2, now - Duration::days(1), 25 * 60),
+            ArchivedNotification::from(cut_short),
+            archived(4, now - Duration::hours(1), 25 * 60),
+            // snoozed break is not a session
+            archived(5, now - Duration::minutes(30), 0),
+            // not finished yet
+            archived(6, now + Duration::hours(1), 25 * 60),
+            archived(7, now - Duration::minutes(10), 25 * 60),
+            ArchivedNotification::from(deleted),
+        ];
+
+        let stats = Statistics::new(&notifications, now, Some(tz), 7);
+
+        assert_eq!(2, stats.today.sessions);
+        assert_eq!(35 * 60, stats.today.focused);
+        assert_eq!(4, stats.this_week.sessions);
+        assert_eq!(85 * 60, stats.this_week.focused);
+        assert_eq!(4, stats.this_month.sessions);
+        assert_eq!((4, 1), (stats.completed, stats.cut_short));
+        assert_eq!(135 * 60 / 5, stats.average_session);
+        assert_eq!((2, 2), (stats.current_streak, stats.longest_streak));
+
+        let daily = stats.get_daily_rows();
+        assert_eq!(7, daily.len());
+        assert_eq!("2026-10-11 (Sun)", daily[0].period);
+        assert_eq!("2026-10-17 (Sat)", daily[6].period);
+        assert_eq!("35m", daily[6].focused);
+        assert_eq!(0, daily[2].sessions);
+
+        let totals = stats.get_total_rows();
+        assert_eq!("1h25m", totals[1].focused);
+
+        let summary = stats.get_summary_rows();
+        assert_eq!("27m", summary[0].value);
+        assert_eq!("2 days", summary[3].value);
+
+        let json = stats.to_json();
+        assert_eq!(json!({ "sessions": 2, "focused": 35 * 60 }), json["today"]);
+        assert_eq!(
+            json!({ "date": "2026-10-17", "sessions": 2, "focused": 35 * 60 }),
+            json["daily"][6]
+        );
+        assert_eq!(json!(2), json["current_streak"]);
+    }
+}
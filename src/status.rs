@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::command::DEFAULT_STATUS_FORMAT;
+use crate::notification::{Notification, NotificationState};
+
+/// The header and the start of the endless array of the i3bar protocol, printed once in --watch mode
+pub const I3BAR_HEADER: &str = "{\"version\":1}\n[";
+
+/// The format of the status line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusFormat {
+    Template(String),
+    Waybar,
+    I3bar,
+}
+
+impl From<&str> for StatusFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "waybar" => StatusFormat::Waybar,
+            "i3bar" => StatusFormat::I3bar,
+            _ => StatusFormat::Template(s.to_string()),
+        }
+    }
+}
+
+/// Status is the active notification shown in status bars.
+/// The running one is active first, then the paused one and then the one starting next.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Status {
+    id: Option<u16>,
+    phase: &'static str,
+    // the seconds left in the phase, or until the start if pending
+    remaining: u32,
+    percentage: i64,
+    description: String,
+    cycle: Option<(u16, u16)>,
+}
+
+impl Status {
+    pub fn new(notifications: &[Notification], now: DateTime<Utc>) -> Self {
+        let active = notifications
+            .iter()
+            .filter_map(|n| match n.get_state(now) {
+                NotificationState::Work | NotificationState::Break => Some((0, n)),
+                NotificationState::Paused => Some((1, n)),
+                NotificationState::Pending => Some((2, n)),
+                NotificationState::Done => None,
+            })
+            .min_by_key(|(order, n)| (*order, n.get_start_at()))
+            .map(|(_, n)| n);
+
+        match active {
+            Some(n) => {
+                // the remaining time is frozen while paused
+                let utc = n.get_paused_at().unwrap_or(now);
+                let (_, _, _, _, _, work_expired_at, break_expired_at) = n.get_values();
+                let until = if utc < n.get_start_at() {
+                    n.get_start_at()
+                } else if utc < work_expired_at {
+                    work_expired_at
+                } else {
+                    break_expired_at
+                };
+
+                Status {
+                    id: Some(n.get_id()),
+                    phase: n.get_state(now).as_str(),
+                    remaining: u32::try_from((until - utc).num_seconds().max(0))
+                        .unwrap_or(u32::MAX),
+                    percentage: n.get_work_percentage_value(utc),
+                    description: n.get_description().to_string(),
+                    cycle: n.get_cycle(),
+                }
+            }
+            None => Status {
+                id: None,
+                phase: "idle",
+                remaining: 0,
+                percentage: 0,
+                description: String::new(),
+                cycle: None,
+            },
+        }
+    }
+
+    /// get_remaining returns the remaining time as mm:ss
+    fn get_remaining(&self) -> String {
+        if self.id.is_none() {
+            return String::from("--:--");
+        }
+
+        format!("{:02}:{:02}", self.remaining / 60, self.remaining % 60)
+    }
+
+    fn get_tooltip(&self) -> String {
+        match self.id {
+            Some(id) => format!(
+                "Notification (id: {}) {}, {}% of work done",
+                id, self.phase, self.percentage
+            ),
+            None => String::from("No active notification"),
+        }
+    }
+
+    fn fill(&self, template: &str) -> String {
+        let cycle = match self.cycle {
+            Some((index, total)) => format!("{}/{}", index, total),
+            None => String::new(),
+        };
+
+        template
+            .replace(
+                "{id}",
+                &self.id.map(|id| id.to_string()).unwrap_or_default(),
+            )
+            .replace("{phase}", self.phase)
+            .replace("{remaining}", &self.get_remaining())
+            .replace("{percentage}", &self.percentage.to_string())
+            .replace("{description}", &self.description)
+            .replace("{cycle}", &cycle)
+            .trim()
+            .to_string()
+    }
+
+    /// render returns the status line, which is a json object for waybar and i3bar
+    pub fn render(&self, format: &StatusFormat) -> String {
+        match format {
+            StatusFormat::Template(template) => self.fill(template),
+            StatusFormat::Waybar => json!({
+                "text": self.fill(DEFAULT_STATUS_FORMAT),
+                "alt": self.phase,
+                "tooltip": self.get_tooltip(),
+                "class": self.phase,
+                "percentage": self.percentage,
+            })
+            .to_string(),
+            StatusFormat::I3bar => json!({
+                "name": "pomodoro",
+                "full_text": self.fill(DEFAULT_STATUS_FORMAT),
+                "short_text": self.get_remaining(),
+            })
+            .to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::{json, Value};
+
+    use super::{Status, StatusFormat};
+    use crate::notification::Notification;
+
+    #[test]
+    fn test_status_format() {
+        assert_eq!(StatusFormat::Waybar, StatusFormat::from("waybar"));
+        assert_eq!(StatusFormat::I3bar, StatusFormat::from("i3bar"));
+        assert_eq!(
+            StatusFormat::Template(String::from("{phase} {remaining}")),
+            StatusFormat::from("{phase} {remaining}")
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        let now = Utc.ymd(2023, 3, 1).and_hms(9, 10, 30);
+        let template = StatusFormat::from("{id} {phase} {remaining} {percentage}% {description}");
+
+        let status = Status::new(&[], now);
+        assert_eq!("idle --:-- 0%", status.render(&template));
+
+        // the running one is active rather than the one starting next
+        let notifications = vec![
+            Notification::new(
+                1,
+                "write docs",
+                25 * 60,
+                5 * 60,
+                now + Duration::minutes(30),
+            ),
+            Notification::new(2, "review PR", 25 * 60, 5 * 60, now - Duration::minutes(10))
+                .with_cycle(1, 4),
+        ];
+        let status = Status::new(&notifications, now);
+        assert_eq!("2 work 15:00 40% review PR", status.render(&template));
+        assert_eq!(
+            "1/4",
+            status.render(&StatusFormat::Template(String::from("{cycle}")))
+        );
+
+        let status = Status::new(&notifications[..1], now);
+        assert_eq!("1 pending 30:00 0% write docs", status.render(&template));
+
+        // the remaining time is frozen while paused
+        let mut paused = Notification::new(3, "", 25 * 60, 5 * 60, now - Duration::minutes(27));
+        paused.pause(now - Duration::minutes(1));
+        let status = Status::new(&[paused], now);
+        assert_eq!("3 paused 04:00 100%", status.render(&template));
+    }
+
+    #[test]
+    fn test_render_bars() {
+        let now = Utc.ymd(2023, 3, 1).and_hms(9, 10, 30);
+        let notifications = vec![Notification::new(
+            2,
+            "review PR",
+            25 * 60,
+            5 * 60,
+            now - Duration::minutes(10),
+        )];
+        let status = Status::new(&notifications, now);
+
+        let waybar: Value = serde_json::from_str(&status.render(&StatusFormat::Waybar)).unwrap();
+        assert_eq!(
+            json!({
+                "text": "work 15:00 review PR",
+                "alt": "work",
+                "tooltip": "Notification (id: 2) work, 40% of work done",
+                "class": "work",
+                "percentage": 40,
+            }),
+            waybar
+        );
+
+        let i3bar: Value = serde_json::from_str(&status.render(&StatusFormat::I3bar)).unwrap();
+        assert_eq!(
+            json!({
+                "name": "pomodoro",
+                "full_text": "work 15:00 review PR",
+                "short_text": "15:00",
+            }),
+            i3bar
+        );
+    }
+}